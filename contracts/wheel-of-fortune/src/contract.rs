@@ -1,27 +1,48 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure_eq, has_coins, to_json_binary, Addr, Api, BalanceResponse, BankMsg, BankQuery, Binary,
-    Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, QueryRequest, Response, StdResult,
-    Storage, Timestamp, Uint128, WasmMsg,
+    attr, ensure, from_json, has_coins, to_json_binary, to_json_string, to_json_vec, Addr, Api,
+    Attribute, BalanceResponse, BankMsg, BankQuery, Binary, BlockInfo, Coin, CosmosMsg, Decimal,
+    Deps, DepsMut, Empty, Env, Event, HexBinary, MessageInfo, Order, QueryRequest, Reply, Response,
+    StdError, StdResult, Storage, SubMsg, SubMsgResult, Timestamp, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
+use serde::de::IgnoredAny;
 
 use cw721::Cw721ExecuteMsg;
+use cw721_base::{
+    ExecuteMsg as Cw721BaseExecuteMsg, Extension as Cw721BaseExtension, MinterResponse,
+    QueryMsg as Cw721BaseQueryMsg,
+};
 
 use cw20::Cw20ExecuteMsg;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, WhiteListResponse};
+use crate::msg::{
+    ConfigUpdate, ExecuteMsg, InstantiateMsg, LeaderboardOrder, MockExecuteMsg, OperatorExecuteMsg,
+    PendingJobResponse, QueryMsg, RandomnessProviderMsg, ReferralRewardsResponse,
+    SlotStatsResponse, SpinResponse, TierResponse, VerifySpinResponse, VestingResponse,
+    VrfCallback, VrfExecuteMsg, WheelStatsResponse, WhiteListResponse, WinnerResponse,
+};
 use crate::state::{
-    AdminConfig, CoinReward, CollectionReward, Config, RandomJob, TextReward, TokenReward,
-    WheelReward, ADMIN_CONFIG, CONFIG, LOCKED_COINS, RANDOM_JOBS, RANDOM_SEED, SPINS_RESULT,
-    WHEEL_REWARDS, WHITELIST,
+    player_scores, spin_records, AdminConfig, Bundle, BundleReward, CoinReward, CollectionReward,
+    Config, ContractCallReward, Jackpot, Leaderboard, MintNftReward, PlayerScore, RandomCoinReward,
+    RandomJob, RandomTokenReward, RandomnessMode, RandomnessProvider, Referral, ReferralReward,
+    SeedCommitment, SpinCallback, SpinCommit, SpinProof, SpinRecord, TextReward, Tier, TokenAsset,
+    TokenReward, VestingAsset, VestingRecord, VestingSchedule, VoucherRedemption, VoucherReward,
+    WheelReward, ADMIN_CONFIG, CALL_TARGETS, CONFIG, FREE_SPINS, JACKPOT, JOB_COUNT, JOB_QUEUE,
+    LEADERBOARD, LOCKED_COINS, LOSING_STREAKS, OPERATORS, RANDOM_JOBS, RANDOM_SEED, REFERRALS,
+    REFERRAL_EARNINGS, REFERRAL_REWARD, SEED_COMMITMENT, SLOT_STATS, SLOT_VALUES, SPIN_COMMITS,
+    SPIN_COUNT, SPIN_PROOFS, TIERS, VESTINGS, VOUCHER_ORACLE, VOUCHER_REDEMPTIONS, WHEEL_REWARDS,
+    WHEEL_STATS, WHITELIST,
 };
+use cw_storage_plus::{Bound, PrefixBound};
+
+use sha2::{Digest, Sha256};
 
 use nois::{
-    int_in_range, randomness_from_str, select_from_weighted, shuffle as nois_shuffle,
-    sub_randomness_with_key, NoisCallback, ProxyExecuteMsg,
+    int_in_range, random_decimal, randomness_from_str, select_from_weighted,
+    shuffle as nois_shuffle, sub_randomness_with_key, NoisCallback, ProxyExecuteMsg,
 };
 
 // version info for migration info
@@ -32,6 +53,17 @@ const MAX_TEXT_LENGTH: usize = 253;
 const MAX_VEC_ITEM: usize = 65536;
 const MAX_SPINS_PER_TURN: u32 = 10;
 const DEFAULT_ACTIVATE: bool = false;
+const JACKPOT_REWARD_ID: u32 = u32::MAX;
+// default seconds after the wheel ends before pending commit-reveal spins can be refunded
+const DEFAULT_REVEAL_TIMEOUT: u64 = 7 * 24 * 60 * 60;
+// seconds after a spin is submitted before it can be refunded if randomness never arrives
+const RANDOMNESS_TIMEOUT: u64 = 60 * 60;
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+// reply id of operator callbacks, their failures do not revert the spin
+const SPIN_CALLBACK_REPLY_ID: u64 = 1;
+// gas an operator callback can use, so it can not fail the spin by running out of gas
+const SPIN_CALLBACK_GAS_LIMIT: u64 = 1_000_000;
 
 /// Handling contract instantiation
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -53,18 +85,53 @@ pub fn instantiate(
         });
     }
 
-    let nois_proxy = addr_validate(deps.api, &msg.nois_proxy)?;
+    if msg.pity_threshold == Some(0) {
+        return Err(ContractError::CustomError {
+            val: "the pity threshold must be greater than 0".to_string(),
+        });
+    }
+
+    if msg.spin_reveal_window == Some(0) {
+        return Err(ContractError::CustomError {
+            val: "the spin reveal window must be greater than 0".to_string(),
+        });
+    }
+
+    if msg.reveal_timeout == Some(0) {
+        return Err(ContractError::CustomError {
+            val: "the reveal timeout must be greater than 0".to_string(),
+        });
+    }
+
+    if msg.is_advanced_randomness && msg.is_commit_reveal {
+        return Err(ContractError::CustomError {
+            val: "only one randomness mode can be enabled".to_string(),
+        });
+    }
+
+    let nois_proxy = msg
+        .nois_proxy
+        .map(|nois_proxy| addr_validate(deps.api, &nois_proxy))
+        .transpose()?;
+
+    let randomness_provider = validate_randomness_provider(deps.api, msg.randomness_provider)?;
 
     let config = Config {
         wheel_name: msg.wheel_name,
         max_spins_per_address: msg.max_spins_per_address,
         is_public: msg.is_public,
         is_advanced_randomness: msg.is_advanced_randomness,
+        is_commit_reveal: msg.is_commit_reveal,
         start_time: None,
         end_time: None,
         price: Coin::default(),
         nois_proxy,
+        randomness_provider,
+        pity_threshold: msg.pity_threshold,
+        spin_reveal_window: msg.spin_reveal_window,
+        reveal_timeout: msg.reveal_timeout,
     };
+    check_randomness_provider(&config)?;
     CONFIG.save(deps.storage, &config)?;
 
     let admin_config = AdminConfig {
@@ -98,6 +165,29 @@ pub fn execute(
         ExecuteMsg::RemoveWhitelist { addresses } => remove_whitelist(deps, info, addresses),
         ExecuteMsg::AddReward { reward } => add_reward(deps, env, info, reward),
         ExecuteMsg::RemoveReward { slot } => remove_reward(deps, info, slot),
+        ExecuteMsg::AddCallTargets { addresses } => add_call_targets(deps, info, addresses),
+        ExecuteMsg::RemoveCallTargets { addresses } => remove_call_targets(deps, info, addresses),
+        ExecuteMsg::AddOperators { addresses } => add_operators(deps, info, addresses),
+        ExecuteMsg::RemoveOperators { addresses } => remove_operators(deps, info, addresses),
+        ExecuteMsg::SetJackpot {
+            label,
+            seed,
+            contribution_rate,
+            probability,
+        } => set_jackpot(deps, info, label, seed, contribution_rate, probability),
+        ExecuteMsg::FundJackpot {} => fund_jackpot(deps, env, info),
+        ExecuteMsg::SetTiers { tiers } => set_tiers(deps, info, tiers),
+        ExecuteMsg::CommitSeed { seed_hash } => commit_seed(deps, info, seed_hash),
+        ExecuteMsg::RevealSeed { seed } => reveal_seed(deps, env, info, seed),
+        ExecuteMsg::SetVoucherOracle { oracle } => set_voucher_oracle(deps, info, oracle),
+        ExecuteMsg::SetLeaderboard {
+            denom,
+            slot_values,
+            bonus,
+        } => set_leaderboard(deps, info, denom, slot_values, bonus),
+        ExecuteMsg::PayLeaderboardBonus {} => pay_leaderboard_bonus(deps, env, info),
+        ExecuteMsg::SetReferralReward { reward } => set_referral_reward(deps, info, reward),
+        ExecuteMsg::UpdateConfig { config } => update_config(deps, env, info, config),
         ExecuteMsg::ActivateWheel {
             price,
             start_time,
@@ -108,13 +198,53 @@ pub fn execute(
         ExecuteMsg::WithdrawCoin { denom, recipient } => {
             withdraw_coin(deps, env, info, denom, recipient)
         }
+        ExecuteMsg::RedeemVoucher {
+            player,
+            reward,
+            code,
+        } => redeem_voucher(deps, env, info, player, reward, code),
 
         // user methods
-        ExecuteMsg::Spin { number } => spin(deps, env, info, number),
+        ExecuteMsg::Spin { number, referrer } => spin(deps, env, info, number, referrer),
+        ExecuteMsg::SpinFor {
+            player,
+            number,
+            callback,
+            referrer,
+        } => spin_for(deps, env, info, player, number, callback, referrer),
+        ExecuteMsg::CommitSpin {
+            number,
+            commitment,
+            referrer,
+        } => commit_spin(deps, env, info, number, commitment, referrer),
+        ExecuteMsg::RevealSpin { secret } => reveal_spin(deps, env, info, secret),
         ExecuteMsg::ClaimReward { rewards } => claim_reward(deps, env, info, rewards),
+        ExecuteMsg::ClaimVested {} => claim_vested(deps, env, info),
+        ExecuteMsg::ClaimReferralEarnings {} => claim_referral_earnings(deps, info),
+        ExecuteMsg::CancelRandomJob { job_id } => cancel_random_job(deps, env, info, job_id),
+        ExecuteMsg::SettleSpin { job_id } => settle_spin(deps, env, job_id),
+        ExecuteMsg::ExpireSpinCommit { player } => expire_spin_commit(deps, env, player),
 
         //nois callback
         ExecuteMsg::NoisReceive { callback } => nois_receive(deps, env, info, callback),
+        ExecuteMsg::VrfReceive { callback } => vrf_receive(deps, env, info, callback),
+        ExecuteMsg::MockReceive { job_id, randomness } => {
+            mock_receive(deps, env, info, job_id, randomness)
+        }
+    }
+}
+
+/// Handling replies of sub messages
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match (msg.id, msg.result) {
+        // a failed operator callback is ignored, the outcome of the spin is already saved
+        (SPIN_CALLBACK_REPLY_ID, SubMsgResult::Err(error)) => Ok(Response::new()
+            .add_attribute("action", "spin_callback_failed")
+            .add_attribute("error", error)),
+        _ => Err(ContractError::CustomError {
+            val: "unknown reply".to_string(),
+        }),
     }
 }
 
@@ -132,9 +262,7 @@ pub fn add_whitelist(
         let addr = Addr::unchecked(address.clone());
 
         if !WHITELIST.has(deps.storage, addr.clone()) {
-            WHITELIST.save(deps.storage, addr.clone(), &0)?;
-
-            SPINS_RESULT.save(deps.storage, addr, &Vec::new())?;
+            WHITELIST.save(deps.storage, addr, &0)?;
         }
     }
 
@@ -158,6 +286,70 @@ pub fn remove_whitelist(
     Ok(Response::new().add_attribute("action", "remove_whitelist"))
 }
 
+pub fn add_call_targets(
+    deps: DepsMut,
+    info: MessageInfo,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    // check if wheel is not activated and sender is contract admin
+    is_not_activate_and_owned(deps.storage, info.sender)?;
+
+    for address in addresses {
+        let addr = addr_validate(deps.api, &address)?;
+
+        CALL_TARGETS.save(deps.storage, addr, &true)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "add_call_targets"))
+}
+
+pub fn remove_call_targets(
+    deps: DepsMut,
+    info: MessageInfo,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    // check if wheel is not activated and sender is contract admin
+    is_not_activate_and_owned(deps.storage, info.sender)?;
+
+    for address in addresses {
+        CALL_TARGETS.remove(deps.storage, Addr::unchecked(address));
+    }
+
+    Ok(Response::new().add_attribute("action", "remove_call_targets"))
+}
+
+pub fn add_operators(
+    deps: DepsMut,
+    info: MessageInfo,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    // check if wheel is not activated and sender is contract admin
+    is_not_activate_and_owned(deps.storage, info.sender)?;
+
+    for address in addresses {
+        let addr = addr_validate(deps.api, &address)?;
+
+        OPERATORS.save(deps.storage, addr, &true)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "add_operators"))
+}
+
+pub fn remove_operators(
+    deps: DepsMut,
+    info: MessageInfo,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    // check if wheel is not activated and sender is contract admin
+    is_not_activate_and_owned(deps.storage, info.sender)?;
+
+    for address in addresses {
+        OPERATORS.remove(deps.storage, Addr::unchecked(address));
+    }
+
+    Ok(Response::new().add_attribute("action", "remove_operators"))
+}
+
 fn add_collection_reward(
     wheel_rewards: &mut Vec<WheelReward>,
     msgs: &mut Vec<CosmosMsg>,
@@ -195,6 +387,8 @@ fn add_token_reward(
         return Err(ContractError::TextTooLong {});
     }
 
+    check_vesting_schedule(&token.vesting)?;
+
     let total_amount = checked_u128_mul_u32(token.amount, token.number);
 
     if total_amount > Uint128::zero() {
@@ -212,6 +406,16 @@ fn add_token_reward(
     Ok(())
 }
 
+fn check_vesting_schedule(vesting: &Option<VestingSchedule>) -> Result<(), ContractError> {
+    if let Some(vesting) = vesting {
+        if vesting.duration == 0 || vesting.cliff > vesting.duration {
+            return Err(ContractError::InvalidVestingSchedule {});
+        }
+    }
+
+    Ok(())
+}
+
 fn add_coin_reward(
     wheel_rewards: &mut Vec<WheelReward>,
     funds: Vec<Coin>,
@@ -221,6 +425,8 @@ fn add_coin_reward(
         return Err(ContractError::TextTooLong {});
     }
 
+    check_vesting_schedule(&coin.vesting)?;
+
     let total_amount = checked_u128_mul_u32(coin.coin.amount, coin.number);
 
     if !has_coins(
@@ -235,6 +441,234 @@ fn add_coin_reward(
     Ok(total_amount)
 }
 
+fn add_bundle_reward(
+    wheel_rewards: &mut Vec<WheelReward>,
+    msgs: &mut Vec<CosmosMsg>,
+    owner: String,
+    recipient: String,
+    funds: Vec<Coin>,
+    bundle: BundleReward,
+) -> Result<Vec<Coin>, ContractError> {
+    if bundle.bundles.len() > MAX_VEC_ITEM {
+        return Err(ContractError::TooManyRewards {});
+    }
+
+    if bundle.label.len() > MAX_TEXT_LENGTH {
+        return Err(ContractError::TextTooLong {});
+    }
+
+    // total assets of all bundles in slot
+    let mut total_tokens: Vec<(String, Uint128)> = Vec::new();
+    let mut total_coins: Vec<Coin> = Vec::new();
+    for item in bundle.bundles.iter() {
+        if item.nfts.is_empty() && item.tokens.is_empty() && item.coins.is_empty() {
+            return Err(ContractError::InvalidBundle {});
+        }
+
+        for nft in item.nfts.iter() {
+            transfer_nft_msgs(
+                msgs,
+                recipient.clone(),
+                nft.collection_address.clone(),
+                vec![nft.token_id.clone()],
+            )?;
+        }
+
+        for token in item.tokens.iter() {
+            if let Some(total) = total_tokens
+                .iter_mut()
+                .find(|(address, _)| *address == token.token_address)
+            {
+                total.1 = total.1.checked_add(token.amount).map_err(StdError::from)?;
+            } else {
+                total_tokens.push((token.token_address.clone(), token.amount));
+            }
+        }
+
+        for coin in item.coins.iter() {
+            if let Some(total) = total_coins.iter_mut().find(|c| c.denom == coin.denom) {
+                total.amount = total
+                    .amount
+                    .checked_add(coin.amount)
+                    .map_err(StdError::from)?;
+            } else {
+                total_coins.push(coin.clone());
+            }
+        }
+    }
+
+    for (token_address, amount) in total_tokens {
+        if amount > Uint128::zero() {
+            transfer_from_token_msg(
+                msgs,
+                owner.clone(),
+                recipient.clone(),
+                token_address,
+                amount,
+            )?;
+        }
+    }
+
+    for coin in total_coins.iter() {
+        if !has_coins(&funds, coin) {
+            return Err(ContractError::InsufficentFund {});
+        }
+    }
+
+    wheel_rewards.push(WheelReward::Bundle(bundle));
+
+    Ok(total_coins)
+}
+
+fn add_random_coin_reward(
+    wheel_rewards: &mut Vec<WheelReward>,
+    funds: Vec<Coin>,
+    coin: RandomCoinReward,
+) -> Result<(), ContractError> {
+    if coin.label.len() > MAX_TEXT_LENGTH {
+        return Err(ContractError::TextTooLong {});
+    }
+
+    if coin.amount.is_some() {
+        return Err(ContractError::CustomError {
+            val: "amount is only set on won rewards".to_string(),
+        });
+    }
+
+    check_random_amount(coin.min, coin.max, coin.pool, coin.number)?;
+
+    if !has_coins(&funds, &Coin::new(coin.pool.u128(), coin.denom.clone())) {
+        return Err(ContractError::InsufficentFund {});
+    }
+
+    wheel_rewards.push(WheelReward::RandomCoin(coin));
+
+    Ok(())
+}
+
+fn add_random_token_reward(
+    wheel_rewards: &mut Vec<WheelReward>,
+    msgs: &mut Vec<CosmosMsg>,
+    owner: String,
+    recipient: String,
+    token: RandomTokenReward,
+) -> Result<(), ContractError> {
+    if token.label.len() > MAX_TEXT_LENGTH {
+        return Err(ContractError::TextTooLong {});
+    }
+
+    if token.amount.is_some() {
+        return Err(ContractError::CustomError {
+            val: "amount is only set on won rewards".to_string(),
+        });
+    }
+
+    check_random_amount(token.min, token.max, token.pool, token.number)?;
+
+    if token.pool > Uint128::zero() {
+        transfer_from_token_msg(
+            msgs,
+            owner,
+            recipient,
+            token.token_address.clone(),
+            token.pool,
+        )?;
+    }
+
+    wheel_rewards.push(WheelReward::RandomToken(token));
+
+    Ok(())
+}
+
+/// Make sure that the pool can pay min amount for every item
+fn check_random_amount(
+    min: Uint128,
+    max: Uint128,
+    pool: Uint128,
+    number: u32,
+) -> Result<(), ContractError> {
+    if min > max || max.is_zero() {
+        return Err(ContractError::InvalidAmountRange {});
+    }
+
+    if pool < checked_u128_mul_u32(min, number) {
+        return Err(ContractError::InsufficentFund {});
+    }
+
+    Ok(())
+}
+
+fn add_mint_nft_reward(
+    wheel_rewards: &mut Vec<WheelReward>,
+    nft: MintNftReward,
+) -> Result<(), ContractError> {
+    if nft.label.len() > MAX_TEXT_LENGTH || nft.token_id_prefix.len() > MAX_TEXT_LENGTH {
+        return Err(ContractError::TextTooLong {});
+    }
+
+    // serials of all nfts in slot must not overflow
+    nft.next_serial
+        .checked_add(nft.number)
+        .ok_or(ContractError::TooManyRewards {})?;
+
+    wheel_rewards.push(WheelReward::MintNft(nft));
+
+    Ok(())
+}
+
+fn add_voucher_reward(
+    wheel_rewards: &mut Vec<WheelReward>,
+    mut voucher: VoucherReward,
+) -> Result<(), ContractError> {
+    if voucher.label.len() > MAX_TEXT_LENGTH {
+        return Err(ContractError::TextTooLong {});
+    }
+
+    if voucher.code_hashes.is_empty() || voucher.code_hashes.len() > MAX_VEC_ITEM {
+        return Err(ContractError::CustomError {
+            val: "invalid number of code hashes".to_string(),
+        });
+    }
+
+    // indexes of all codes in slot must not overflow
+    voucher
+        .next_index
+        .checked_add(voucher.code_hashes.len() as u32)
+        .ok_or(ContractError::TooManyRewards {})?;
+
+    // code hashes must be sha256 hex strings
+    for hash in voucher.code_hashes.iter_mut() {
+        if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ContractError::InvalidCodeHash {});
+        }
+        *hash = hash.to_lowercase();
+    }
+
+    wheel_rewards.push(WheelReward::Voucher(voucher));
+
+    Ok(())
+}
+
+fn add_contract_call_reward(
+    wheel_rewards: &mut Vec<WheelReward>,
+    call: ContractCallReward,
+) -> Result<(), ContractError> {
+    if call.label.len() > MAX_TEXT_LENGTH {
+        return Err(ContractError::TextTooLong {});
+    }
+
+    // message must be a valid json after templating
+    from_json::<IgnoredAny>(call.msg.replace("{winner}", "winner").as_bytes()).map_err(|_| {
+        ContractError::CustomError {
+            val: "invalid contract call message".to_string(),
+        }
+    })?;
+
+    wheel_rewards.push(WheelReward::ContractCall(call));
+
+    Ok(())
+}
+
 fn add_text_reward(
     wheel_rewards: &mut Vec<WheelReward>,
     text: TextReward,
@@ -264,6 +698,11 @@ pub fn add_reward(
         return Err(ContractError::TooManySlots {});
     }
 
+    // the jackpot slot id is reserved for jackpot wins
+    if reward.get_id() == JACKPOT_REWARD_ID {
+        return Err(ContractError::InvalidSlotReward {});
+    }
+
     let mut msgs: Vec<CosmosMsg> = Vec::new();
 
     match reward {
@@ -304,17 +743,7 @@ pub fn add_reward(
 
             // Locked coins can only be claimed by users who win rewards
             // and by the owner at the end of the spin through the `withdraw` method
-            if let Some(locked_amount) =
-                LOCKED_COINS.may_load(deps.storage, coin.coin.denom.clone())?
-            {
-                LOCKED_COINS.save(
-                    deps.storage,
-                    coin.coin.denom,
-                    &locked_amount.checked_add(total_amount).unwrap(),
-                )?;
-            } else {
-                LOCKED_COINS.save(deps.storage, coin.coin.denom, &total_amount)?;
-            }
+            lock_coin(deps.storage, coin.coin.denom, total_amount)?;
         }
         WheelReward::Text(text) => {
             supply = checked_add_supply(supply, text.number)?;
@@ -322,33 +751,120 @@ pub fn add_reward(
             // add text to wheel rewards list
             add_text_reward(wheel_rewards.as_mut(), text)?;
         }
-    }
+        WheelReward::RandomCoin(coin) => {
+            supply = checked_add_supply(supply, coin.number)?;
 
-    WHEEL_REWARDS.save(deps.storage, &(supply, wheel_rewards))?;
+            // add random coin to wheel rewards list
+            add_random_coin_reward(wheel_rewards.as_mut(), info.funds, coin.clone())?;
 
-    if msgs.len() > 0 {
-        Ok(Response::new()
-            .add_attribute("action", "add_rewards")
-            .add_messages(msgs))
-    } else {
-        Ok(Response::new().add_attribute("action", "add_rewards"))
-    }
-}
+            // the whole pool is locked as same as coin rewards
+            lock_coin(deps.storage, coin.denom, coin.pool)?;
+        }
+        WheelReward::RandomToken(token) => {
+            addr_validate(deps.api, &token.token_address)?;
 
-fn remove_reward(deps: DepsMut, info: MessageInfo, slot: u32) -> Result<Response, ContractError> {
-    // check if wheel is not activated and sender is contract admin
-    is_not_activate_and_owned(deps.storage, info.sender.clone())?;
+            supply = checked_add_supply(supply, token.number)?;
 
-    // list rewards of the wheel
-    let (supply, mut wheel_rewards) = WHEEL_REWARDS.load(deps.storage)?;
+            // add random token to wheel rewards list
+            add_random_token_reward(
+                wheel_rewards.as_mut(),
+                msgs.as_mut(),
+                info.sender.to_string(),
+                env.contract.address.to_string(),
+                token,
+            )?;
+        }
+        WheelReward::MintNft(nft) => {
+            addr_validate(deps.api, &nft.collection_address)?;
 
-    // slot out of range
-    if (slot as usize) >= wheel_rewards.len() {
-        return Err(ContractError::InvalidSlotReward {});
-    }
+            // the wheel must have minter rights on collection
+            let minter: MinterResponse = deps.querier.query_wasm_smart(
+                nft.collection_address.clone(),
+                &Cw721BaseQueryMsg::<Empty>::Minter {},
+            )?;
+            if minter.minter != Some(env.contract.address.to_string()) {
+                return Err(ContractError::NotMinter {});
+            }
 
-    // get and remove reward at slot
-    let reward = wheel_rewards.remove(slot as usize);
+            supply = checked_add_supply(supply, nft.number)?;
+
+            // add mint nft to wheel rewards list
+            add_mint_nft_reward(wheel_rewards.as_mut(), nft)?;
+        }
+        WheelReward::ContractCall(call) => {
+            // only registered contracts can be called
+            let contract_address = addr_validate(deps.api, &call.contract_address)?;
+            if !CALL_TARGETS.has(deps.storage, contract_address) {
+                return Err(ContractError::CallTargetNotAllowed {});
+            }
+
+            supply = checked_add_supply(supply, call.number)?;
+
+            // add contract call to wheel rewards list
+            add_contract_call_reward(wheel_rewards.as_mut(), call)?;
+        }
+        WheelReward::Voucher(voucher) => {
+            supply = checked_add_supply(supply, voucher.code_hashes.len() as u32)?;
+
+            // add voucher to wheel rewards list
+            add_voucher_reward(wheel_rewards.as_mut(), voucher)?;
+        }
+        WheelReward::Bundle(bundle) => {
+            // validate contract address of all assets in bundles
+            for item in bundle.bundles.iter() {
+                for nft in item.nfts.iter() {
+                    addr_validate(deps.api, &nft.collection_address)?;
+                }
+                for token in item.tokens.iter() {
+                    addr_validate(deps.api, &token.token_address)?;
+                }
+            }
+
+            supply = checked_add_supply(supply, bundle.bundles.len() as u32)?;
+
+            // add bundle to wheel rewards list
+            let total_coins = add_bundle_reward(
+                wheel_rewards.as_mut(),
+                msgs.as_mut(),
+                info.sender.to_string(),
+                env.contract.address.to_string(),
+                info.funds,
+                bundle,
+            )?;
+
+            // coins in bundles are locked as same as coin rewards
+            for coin in total_coins {
+                lock_coin(deps.storage, coin.denom, coin.amount)?;
+            }
+        }
+    }
+
+    WHEEL_REWARDS.save(deps.storage, &(supply, wheel_rewards))?;
+
+    if !msgs.is_empty() {
+        Ok(Response::new()
+            .add_attribute("action", "add_rewards")
+            .add_messages(msgs))
+    } else {
+        Ok(Response::new().add_attribute("action", "add_rewards"))
+    }
+}
+
+fn remove_reward(deps: DepsMut, info: MessageInfo, slot: u32) -> Result<Response, ContractError> {
+    // check if wheel is not activated and sender is contract admin
+    is_not_activate_and_owned(deps.storage, info.sender.clone())?;
+
+    // list rewards of the wheel
+    let (supply, mut wheel_rewards) = WHEEL_REWARDS.load(deps.storage)?;
+
+    // slot out of range
+    if (slot as usize) >= wheel_rewards.len() {
+        return Err(ContractError::InvalidSlotReward {});
+    }
+
+    // get and remove reward at slot
+    let reward = wheel_rewards.remove(slot as usize);
+    SLOT_VALUES.remove(deps.storage, reward.get_id());
 
     let mut msgs: Vec<CosmosMsg> = Vec::new();
 
@@ -358,211 +874,1584 @@ fn remove_reward(deps: DepsMut, info: MessageInfo, slot: u32) -> Result<Response
     // update wheel rewards
     WHEEL_REWARDS.save(deps.storage, &(supply - removed_supply, wheel_rewards))?;
 
-    if msgs.len() > 0 {
-        return Ok(Response::new()
+    if !msgs.is_empty() {
+        Ok(Response::new()
             .add_attribute("action", "remove_reward")
             .add_attribute("slot", slot.to_string())
-            .add_messages(msgs));
+            .add_messages(msgs))
     } else {
-        return Ok(Response::new()
+        Ok(Response::new()
             .add_attribute("action", "remove_reward")
-            .add_attribute("slot", slot.to_string()));
+            .add_attribute("slot", slot.to_string()))
     }
 }
 
-pub fn activate_wheel(
+/// Shares of the spin fee must not exceed the fee
+fn check_fee_rates(
+    fee_share_rate: Decimal,
+    contribution_rate: Decimal,
+) -> Result<(), ContractError> {
+    if fee_share_rate
+        .checked_add(contribution_rate)
+        .map_err(StdError::from)?
+        > Decimal::one()
+    {
+        return Err(ContractError::CustomError {
+            val: "the sum of fee share rate and contribution rate must not be greater than 1"
+                .to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+pub fn set_jackpot(
+    deps: DepsMut,
+    info: MessageInfo,
+    label: String,
+    seed: Coin,
+    contribution_rate: Decimal,
+    probability: Decimal,
+) -> Result<Response, ContractError> {
+    // check if wheel is not activated and sender is contract admin
+    is_not_activate_and_owned(deps.storage, info.sender.clone())?;
+
+    if label.len() > MAX_TEXT_LENGTH {
+        return Err(ContractError::TextTooLong {});
+    }
+
+    if JACKPOT.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::CustomError {
+            val: "the jackpot is already set".to_string(),
+        });
+    }
+
+    let fee_share_rate = match REFERRAL_REWARD.may_load(deps.storage)? {
+        Some(ReferralReward::FeeShare { rate }) => rate,
+        _ => Decimal::zero(),
+    };
+    check_fee_rates(fee_share_rate, contribution_rate)?;
+
+    if probability.is_zero() || probability > Decimal::one() {
+        return Err(ContractError::CustomError {
+            val: "the jackpot probability must be in range (0, 1]".to_string(),
+        });
+    }
+
+    // the initial pool is funded by admin
+    if !has_coins(&info.funds, &seed) {
+        return Err(ContractError::InsufficentFund {});
+    }
+
+    // the pool is promised to the winner so it can not be withdrawn by `withdraw_coin`
+    lock_coin(deps.storage, seed.denom.clone(), seed.amount)?;
+
+    let jackpot = Jackpot {
+        label,
+        denom: seed.denom,
+        seed_amount: seed.amount,
+        contribution_rate,
+        probability,
+        pool: seed.amount,
+        reserve: Uint128::zero(),
+    };
+    JACKPOT.save(deps.storage, &jackpot)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_jackpot")
+        .add_attribute("pool", jackpot.pool))
+}
+
+pub fn set_leaderboard(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    slot_values: Vec<(u32, Uint128)>,
+    bonus: Vec<Uint128>,
+) -> Result<Response, ContractError> {
+    // check if wheel is not activated and sender is contract admin
+    is_not_activate_and_owned(deps.storage, info.sender.clone())?;
+
+    if LEADERBOARD.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::CustomError {
+            val: "the leaderboard is already set".to_string(),
+        });
+    }
+
+    // only slots of the wheel (or the jackpot when it is set) can be valued
+    let (_, wheel_rewards) = WHEEL_REWARDS.load(deps.storage)?;
+    let has_jackpot = JACKPOT.may_load(deps.storage)?.is_some();
+    for (slot_id, value) in slot_values {
+        let is_valid_slot = wheel_rewards
+            .iter()
+            .any(|reward| reward.get_id() == slot_id)
+            || (has_jackpot && slot_id == JACKPOT_REWARD_ID);
+        if !is_valid_slot {
+            return Err(ContractError::InvalidSlotReward {});
+        }
+
+        SLOT_VALUES.save(deps.storage, slot_id, &value)?;
+    }
+
+    // the bonus is funded by admin and locked until it is paid
+    let total_bonus = sum_bonus(&bonus)?;
+    if !total_bonus.is_zero() {
+        if !has_coins(&info.funds, &Coin::new(total_bonus.u128(), denom.clone())) {
+            return Err(ContractError::InsufficentFund {});
+        }
+
+        lock_coin(deps.storage, denom.clone(), total_bonus)?;
+    }
+
+    LEADERBOARD.save(
+        deps.storage,
+        &Leaderboard {
+            denom,
+            bonus,
+            is_bonus_paid: false,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_leaderboard")
+        .add_attribute("bonus", total_bonus))
+}
+
+pub fn pay_leaderboard_bonus(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    price: Coin,
-    start_time: Option<Timestamp>,
-    end_time: Timestamp,
-    shuffle: Option<bool>,
+) -> Result<Response, ContractError> {
+    // check if wheel is activated and sender is contract admin
+    is_activate_and_owned(deps.storage, info.sender.clone())?;
+
+    // bonus is only paid when the round is over
+    let config = CONFIG.load(deps.storage)?;
+    if config.end_time.unwrap() >= env.block.time {
+        return Err(ContractError::WheelNotEnded {});
+    }
+
+    // pending spins can still change the ranking
+    if has_pending_spins(deps.storage) {
+        return Err(ContractError::PendingSpins {});
+    }
+
+    let mut leaderboard = if let Some(leaderboard) = LEADERBOARD.may_load(deps.storage)? {
+        leaderboard
+    } else {
+        return Err(ContractError::CustomError {
+            val: "the leaderboard is not set".to_string(),
+        });
+    };
+
+    if leaderboard.is_bonus_paid {
+        return Err(ContractError::CustomError {
+            val: "the leaderboard bonus is already paid".to_string(),
+        });
+    }
+
+    // players without any score are not ranked
+    let top_players: Vec<Addr> = player_scores()
+        .idx
+        .value
+        .range(deps.storage, None, None, Order::Descending)
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |(_, score)| !score.value.is_zero())
+        })
+        .take(leaderboard.bonus.len())
+        .map(|item| item.map(|(player, _)| player))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    let mut total_bonus = Uint128::zero();
+
+    let mut events: Vec<Event> = Vec::new();
+
+    for (rank, (player, amount)) in top_players
+        .into_iter()
+        .zip(leaderboard.bonus.iter())
+        .enumerate()
+    {
+        if !amount.is_zero() {
+            events.push(
+                Event::new("leaderboard_bonus")
+                    .add_attribute("player", player.to_string())
+                    .add_attribute("rank", (rank + 1).to_string())
+                    .add_attribute("asset", leaderboard.denom.clone())
+                    .add_attribute("amount", amount.to_string()),
+            );
+
+            send_coin_msg(
+                msgs.as_mut(),
+                player.to_string(),
+                vec![Coin::new(amount.u128(), leaderboard.denom.clone())],
+            )?;
+        }
+        total_bonus = total_bonus.checked_add(*amount).map_err(StdError::from)?;
+    }
+
+    // bonus of ranks without player can be withdrawn by admin
+    let locked_bonus = sum_bonus(&leaderboard.bonus)?;
+    if !locked_bonus.is_zero() {
+        unlock_coin(deps.storage, leaderboard.denom.clone(), locked_bonus)?;
+    }
+
+    leaderboard.is_bonus_paid = true;
+    LEADERBOARD.save(deps.storage, &leaderboard)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pay_leaderboard_bonus")
+        .add_attribute("bonus", total_bonus)
+        .add_events(events)
+        .add_messages(msgs))
+}
+
+fn sum_bonus(bonus: &[Uint128]) -> StdResult<Uint128> {
+    bonus.iter().try_fold(Uint128::zero(), |total, amount| {
+        total.checked_add(*amount).map_err(StdError::from)
+    })
+}
+
+pub fn fund_jackpot(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let admin_config = ADMIN_CONFIG.load(deps.storage)?;
+    if admin_config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    if let Some(end_time) = config.end_time {
+        if end_time < env.block.time {
+            return Err(ContractError::WheelEnded {});
+        }
+    }
+
+    let mut jackpot = if let Some(jackpot) = JACKPOT.may_load(deps.storage)? {
+        jackpot
+    } else {
+        return Err(ContractError::JackpotNotFound {});
+    };
+
+    if info.funds.iter().any(|c| c.denom != jackpot.denom) {
+        return Err(ContractError::CustomError {
+            val: "only the jackpot denom can be funded".to_string(),
+        });
+    }
+
+    let amount = if let Some(coin) = info.funds.iter().find(|c| c.denom == jackpot.denom) {
+        coin.amount
+    } else {
+        return Err(ContractError::InsufficentFund {});
+    };
+
+    // funded coins are used to reseed the pool after each win
+    jackpot.reserve = jackpot
+        .reserve
+        .checked_add(amount)
+        .map_err(StdError::from)?;
+    lock_coin(deps.storage, jackpot.denom.clone(), amount)?;
+    JACKPOT.save(deps.storage, &jackpot)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_jackpot")
+        .add_attribute("reserve", jackpot.reserve))
+}
+
+pub fn set_tiers(
+    deps: DepsMut,
+    info: MessageInfo,
+    tiers: Vec<Tier>,
 ) -> Result<Response, ContractError> {
     // check if wheel is not activated and sender is contract admin
     is_not_activate_and_owned(deps.storage, info.sender)?;
 
-    if let Some(start_time) = start_time {
-        if start_time >= end_time {
-            return Err(ContractError::InvalidTimeSetting {});
+    let mut total_weight: u32 = 0;
+    for (idx, tier) in tiers.iter().enumerate() {
+        if tier.name.len() > MAX_TEXT_LENGTH {
+            return Err(ContractError::TextTooLong {});
+        }
+
+        if tier.weight == 0 || tiers[..idx].iter().any(|t| t.name == tier.name) {
+            return Err(ContractError::InvalidTier {});
+        }
+
+        // total weight must not exceed u32::MAX for NOIS function `select_from_weighted`
+        total_weight = total_weight
+            .checked_add(tier.weight)
+            .ok_or(ContractError::InvalidTier {})?;
+    }
+
+    TIERS.save(deps.storage, &tiers)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_tiers")
+        .add_attribute("tiers", tiers.len().to_string()))
+}
+
+pub fn set_voucher_oracle(
+    deps: DepsMut,
+    info: MessageInfo,
+    oracle: Option<String>,
+) -> Result<Response, ContractError> {
+    let admin_config = ADMIN_CONFIG.load(deps.storage)?;
+    if admin_config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match oracle {
+        Some(oracle) => {
+            let oracle = addr_validate(deps.api, &oracle)?;
+            VOUCHER_ORACLE.save(deps.storage, &oracle)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "set_voucher_oracle")
+                .add_attribute("oracle", oracle))
+        }
+        None => {
+            VOUCHER_ORACLE.remove(deps.storage);
+
+            Ok(Response::new().add_attribute("action", "set_voucher_oracle"))
+        }
+    }
+}
+
+pub fn commit_seed(
+    deps: DepsMut,
+    info: MessageInfo,
+    seed_hash: String,
+) -> Result<Response, ContractError> {
+    // check if wheel is not activated and sender is contract admin
+    is_not_activate_and_owned(deps.storage, info.sender)?;
+
+    if !CONFIG.load(deps.storage)?.is_commit_reveal {
+        return Err(ContractError::CustomError {
+            val: "the wheel is not in commit-reveal mode".to_string(),
+        });
+    }
+
+    // seed hash must be sha256 hex string
+    if seed_hash.len() != 64 || !seed_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::InvalidSeed {});
+    }
+
+    SEED_COMMITMENT.save(
+        deps.storage,
+        &SeedCommitment {
+            seed_hash: seed_hash.to_lowercase(),
+            seed: None,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "commit_seed")
+        .add_attribute("seed_hash", seed_hash.to_lowercase()))
+}
+
+pub fn reveal_seed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    seed: String,
+) -> Result<Response, ContractError> {
+    // check if wheel is activated and sender is contract admin
+    is_activate_and_owned(deps.storage, info.sender)?;
+
+    // seed is only revealed when no more spins can be made
+    let config = CONFIG.load(deps.storage)?;
+    if config.end_time.unwrap() >= env.block.time {
+        return Err(ContractError::WheelNotEnded {});
+    }
+
+    let mut commitment = SEED_COMMITMENT.load(deps.storage)?;
+    if commitment.seed.is_some() {
+        return Err(ContractError::CustomError {
+            val: "the seed is already revealed".to_string(),
+        });
+    }
+
+    let randomness =
+        randomness_from_str(seed.clone()).map_err(|_| ContractError::InvalidSeed {})?;
+    if hex::encode(Sha256::digest(randomness)) != commitment.seed_hash {
+        return Err(ContractError::InvalidSeed {});
+    }
+
+    commitment.seed = Some(seed);
+    SEED_COMMITMENT.save(deps.storage, &commitment)?;
+
+    Ok(Response::new().add_attribute("action", "reveal_seed"))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    let admin_config = ADMIN_CONFIG.load(deps.storage)?;
+    if admin_config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if admin_config.activate {
+        // only end time, max spins per address and nois proxy can be updated after activation
+        let locked_field = [
+            ("wheel_name", update.wheel_name.is_some()),
+            ("is_public", update.is_public.is_some()),
+            (
+                "is_advanced_randomness",
+                update.is_advanced_randomness.is_some(),
+            ),
+            ("is_commit_reveal", update.is_commit_reveal.is_some()),
+            ("randomness_provider", update.randomness_provider.is_some()),
+            ("pity_threshold", update.pity_threshold.is_some()),
+            ("spin_reveal_window", update.spin_reveal_window.is_some()),
+            ("reveal_timeout", update.reveal_timeout.is_some()),
+        ]
+        .into_iter()
+        .find(|(_, is_updated)| *is_updated);
+
+        if let Some((field, _)) = locked_field {
+            return Err(ContractError::CustomError {
+                val: format!("{} can not be updated after activation", field),
+            });
+        }
+    } else if update.end_time.is_some() {
+        return Err(ContractError::CustomError {
+            val: "end time is set when the wheel is activated".to_string(),
+        });
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let mut events: Vec<Event> = Vec::new();
+
+    if let Some(wheel_name) = update.wheel_name {
+        if wheel_name.len() > MAX_TEXT_LENGTH {
+            return Err(ContractError::TextTooLong {});
+        }
+
+        events.push(config_event("wheel_name", &config.wheel_name, &wheel_name));
+        config.wheel_name = wheel_name;
+    }
+
+    if let Some(max_spins_per_address) = update.max_spins_per_address {
+        if max_spins_per_address == 0 {
+            return Err(ContractError::CustomError {
+                val: "the maximum number of spins must be greater than 0".to_string(),
+            });
+        }
+
+        // players may have used their spins, so the limit can only be raised
+        if admin_config.activate && max_spins_per_address < config.max_spins_per_address {
+            return Err(ContractError::CustomError {
+                val: "the maximum number of spins can only be raised".to_string(),
+            });
+        }
+
+        events.push(config_event(
+            "max_spins_per_address",
+            config.max_spins_per_address,
+            max_spins_per_address,
+        ));
+        config.max_spins_per_address = max_spins_per_address;
+    }
+
+    if let Some(is_public) = update.is_public {
+        events.push(config_event("is_public", config.is_public, is_public));
+        config.is_public = is_public;
+    }
+
+    if let Some(is_advanced_randomness) = update.is_advanced_randomness {
+        events.push(config_event(
+            "is_advanced_randomness",
+            config.is_advanced_randomness,
+            is_advanced_randomness,
+        ));
+        config.is_advanced_randomness = is_advanced_randomness;
+    }
+
+    if let Some(is_commit_reveal) = update.is_commit_reveal {
+        events.push(config_event(
+            "is_commit_reveal",
+            config.is_commit_reveal,
+            is_commit_reveal,
+        ));
+        config.is_commit_reveal = is_commit_reveal;
+    }
+
+    if config.is_advanced_randomness && config.is_commit_reveal {
+        return Err(ContractError::CustomError {
+            val: "only one randomness mode can be enabled".to_string(),
+        });
+    }
+
+    if let Some(nois_proxy) = update.nois_proxy {
+        let nois_proxy = addr_validate(deps.api, &nois_proxy)?;
+
+        // randomness of pending jobs is sent by the current proxy
+        if RANDOM_JOBS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+        {
+            return Err(ContractError::PendingSpins {});
+        }
+
+        events.push(config_event(
+            "nois_proxy",
+            to_json_string(&config.nois_proxy)?,
+            &nois_proxy,
+        ));
+        config.nois_proxy = Some(nois_proxy);
+    }
+
+    if let Some(randomness_provider) = update.randomness_provider {
+        let randomness_provider = validate_randomness_provider(deps.api, randomness_provider)?;
+
+        events.push(config_event(
+            "randomness_provider",
+            to_json_string(&config.randomness_provider)?,
+            to_json_string(&randomness_provider)?,
+        ));
+        config.randomness_provider = randomness_provider;
+    }
+
+    if let Some(pity_threshold) = update.pity_threshold {
+        let pity_threshold = pity_threshold.into_option();
+        if pity_threshold == Some(0) {
+            return Err(ContractError::CustomError {
+                val: "the pity threshold must be greater than 0".to_string(),
+            });
+        }
+
+        events.push(config_event(
+            "pity_threshold",
+            to_json_string(&config.pity_threshold)?,
+            to_json_string(&pity_threshold)?,
+        ));
+        config.pity_threshold = pity_threshold;
+    }
+
+    if let Some(spin_reveal_window) = update.spin_reveal_window {
+        let spin_reveal_window = spin_reveal_window.into_option();
+        if spin_reveal_window == Some(0) {
+            return Err(ContractError::CustomError {
+                val: "the spin reveal window must be greater than 0".to_string(),
+            });
+        }
+
+        events.push(config_event(
+            "spin_reveal_window",
+            to_json_string(&config.spin_reveal_window)?,
+            to_json_string(&spin_reveal_window)?,
+        ));
+        config.spin_reveal_window = spin_reveal_window;
+    }
+
+    if let Some(reveal_timeout) = update.reveal_timeout {
+        let reveal_timeout = reveal_timeout.into_option();
+        if reveal_timeout == Some(0) {
+            return Err(ContractError::CustomError {
+                val: "the reveal timeout must be greater than 0".to_string(),
+            });
+        }
+
+        events.push(config_event(
+            "reveal_timeout",
+            to_json_string(&config.reveal_timeout)?,
+            to_json_string(&reveal_timeout)?,
+        ));
+        config.reveal_timeout = reveal_timeout;
+    }
+
+    if let Some(end_time) = update.end_time {
+        let current_end_time = config.end_time.unwrap();
+
+        // an ended round can not be reopened
+        if current_end_time < env.block.time {
+            return Err(ContractError::WheelEnded {});
+        }
+
+        if end_time <= current_end_time {
+            return Err(ContractError::CustomError {
+                val: "end time can only be extended".to_string(),
+            });
+        }
+
+        events.push(config_event(
+            "end_time",
+            current_end_time.seconds(),
+            end_time.seconds(),
+        ));
+        config.end_time = Some(end_time);
+    }
+
+    check_randomness_provider(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_events(events))
+}
+
+/// Validate oracle address of a randomness provider
+fn validate_randomness_provider(
+    api: &dyn Api,
+    randomness_provider: RandomnessProviderMsg,
+) -> Result<RandomnessProvider, ContractError> {
+    Ok(match randomness_provider {
+        RandomnessProviderMsg::Nois {} => RandomnessProvider::Nois {},
+        RandomnessProviderMsg::Vrf { oracle } => RandomnessProvider::Vrf {
+            oracle: addr_validate(api, &oracle)?,
+        },
+        RandomnessProviderMsg::Mock { oracle } => RandomnessProvider::Mock {
+            oracle: addr_validate(api, &oracle)?,
+        },
+    })
+}
+
+/// The nois provider can only be used in advanced mode if the nois proxy is set
+fn check_randomness_provider(config: &Config) -> Result<(), ContractError> {
+    if config.is_advanced_randomness
+        && config.randomness_provider == (RandomnessProvider::Nois {})
+        && config.nois_proxy.is_none()
+    {
+        return Err(ContractError::CustomError {
+            val: "the nois proxy is required by the nois randomness provider".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Event of a changed config field
+fn config_event(field: &str, old: impl ToString, new: impl ToString) -> Event {
+    Event::new("config_update")
+        .add_attribute("field", field)
+        .add_attribute("old", old.to_string())
+        .add_attribute("new", new.to_string())
+}
+
+pub fn activate_wheel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    price: Coin,
+    start_time: Option<Timestamp>,
+    end_time: Timestamp,
+    shuffle: Option<bool>,
+) -> Result<Response, ContractError> {
+    // check if wheel is not activated and sender is contract admin
+    is_not_activate_and_owned(deps.storage, info.sender)?;
+
+    if let Some(start_time) = start_time {
+        if start_time >= end_time {
+            return Err(ContractError::InvalidTimeSetting {});
+        }
+    }
+
+    if end_time <= env.block.time {
+        return Err(ContractError::WheelEnded {});
+    }
+
+    // if the wheel has tiers, every slot must belong to one of them
+    let tiers = TIERS.may_load(deps.storage)?.unwrap_or_default();
+    if !tiers.is_empty() {
+        let (_, wheel_rewards) = WHEEL_REWARDS.load(deps.storage)?;
+        for reward in wheel_rewards.iter() {
+            let tier = reward.get_tier();
+            if !tiers.iter().any(|t| Some(&t.name) == tier.as_ref()) {
+                return Err(ContractError::InvalidTier {});
+            }
+        }
+    }
+
+    // in commit-reveal mode, the seed must be committed before any spin
+    if CONFIG.load(deps.storage)?.is_commit_reveal && !SEED_COMMITMENT.exists(deps.storage) {
+        return Err(ContractError::CustomError {
+            val: "the seed hash must be committed before activation".to_string(),
+        });
+    }
+
+    let mut admin_config: AdminConfig = ADMIN_CONFIG.load(deps.storage)?;
+
+    admin_config.activate = true;
+    ADMIN_CONFIG.save(deps.storage, &admin_config)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.price = price;
+    config.start_time = start_time;
+    config.end_time = Some(end_time);
+    CONFIG.save(deps.storage, &config)?;
+
+    let shuffle = shuffle.unwrap_or(false);
+    // if required, shuffle wheel rewards
+    if shuffle {
+        let random_seend = RANDOM_SEED.load(deps.storage)?;
+        let (supply, wheel_rewards) = WHEEL_REWARDS.load(deps.storage)?;
+
+        let wheel_rewards_shuffled = nois_shuffle(random_seend, wheel_rewards);
+
+        // save rewards after shuffled
+        WHEEL_REWARDS.save(deps.storage, &(supply, wheel_rewards_shuffled))?;
+    }
+
+    Ok(Response::new().add_attribute("action", "activate_wheel"))
+}
+
+pub fn set_referral_reward(
+    deps: DepsMut,
+    info: MessageInfo,
+    reward: Option<ReferralReward>,
+) -> Result<Response, ContractError> {
+    // check if wheel is not activated and sender is contract admin
+    is_not_activate_and_owned(deps.storage, info.sender)?;
+
+    match reward {
+        Some(reward) => {
+            match reward {
+                ReferralReward::FeeShare { rate } => {
+                    // fee share and jackpot contribution are both taken from the spin fee
+                    let contribution_rate = JACKPOT
+                        .may_load(deps.storage)?
+                        .map(|jackpot| jackpot.contribution_rate)
+                        .unwrap_or_default();
+                    check_fee_rates(rate, contribution_rate)?;
+                }
+                ReferralReward::FreeSpins { spins } => {
+                    if spins == 0 {
+                        return Err(ContractError::CustomError {
+                            val: "free spins reward must be greater than 0".to_string(),
+                        });
+                    }
+                }
+            }
+            REFERRAL_REWARD.save(deps.storage, &reward)?;
+        }
+        None => REFERRAL_REWARD.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "set_referral_reward"))
+}
+
+/// Check if player can make the number of spins, then consume their spin count and wheel supply.
+/// Free spins of player are only used if `use_free_spins` is set.
+/// Returns config, number of spins, number of free spins used, player's spin count before and remaining funds
+fn take_spins(
+    storage: &mut dyn Storage,
+    env: &Env,
+    player: &Addr,
+    mut funds: Vec<Coin>,
+    number: Option<u32>,
+    use_free_spins: bool,
+) -> Result<(Config, u32, u32, u32, Vec<Coin>), ContractError> {
+    let admin_config = ADMIN_CONFIG.load(storage)?;
+    if !admin_config.activate {
+        return Err(ContractError::WheelNotActivated {});
+    }
+
+    let config = CONFIG.load(storage)?;
+
+    let spins = number.unwrap_or(1);
+    if spins == 0 || spins > MAX_SPINS_PER_TURN {
+        return Err(ContractError::InvalidNumberSpins {});
+    }
+
+    // Check if the wheel has enough rewards
+    // In basic random mode, this check is unnecessary because NOIS function `selected_from_weighted` has checkpoint for this situation
+    // But in advanced random mode, we need this to ensure reward always sufficient
+    let (supply, wheel_rewards) = WHEEL_REWARDS.load(storage)?;
+    if supply < spins {
+        return Err(ContractError::InsufficentReward {});
+    }
+
+    let spinned_result = WHITELIST.may_load(storage, player.clone())?;
+
+    // If the wheel is private, only the whitelist is allowed to spin
+    if !config.is_public && spinned_result.is_none() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(start_time) = config.start_time {
+        if start_time > env.block.time {
+            return Err(ContractError::WheelNotStarted {});
+        }
+    }
+
+    if config.end_time.unwrap() < env.block.time {
+        return Err(ContractError::WheelEnded {});
+    }
+
+    let spinned = spinned_result.unwrap_or(0);
+
+    // free spins of player are used before paid spins
+    let free_spins = if use_free_spins {
+        FREE_SPINS
+            .may_load(storage, player.clone())?
+            .unwrap_or(0)
+            .min(spins)
+    } else {
+        0
+    };
+    if free_spins > 0 {
+        FREE_SPINS.update(storage, player.clone(), |remaining| -> StdResult<_> {
+            Ok(remaining.unwrap_or(0) - free_spins)
+        })?;
+    }
+    let paid_spins = spins - free_spins;
+
+    // check funds
+    check_funds(funds.as_mut(), paid_spins, config.clone())?;
+
+    // shares of spin payment go to the jackpot pool and the referrer
+    // pending spins only contribute when they are settled
+    if !config.is_advanced_randomness && !config.is_commit_reveal {
+        distribute_payment(storage, player, &config.price, paid_spins)?;
+    }
+
+    if spins > (config.max_spins_per_address - spinned) {
+        return Err(ContractError::CustomError {
+            val: format!(
+                "Too many spins request: {} left",
+                config.max_spins_per_address - spinned
+            ),
+        });
+    }
+
+    WHITELIST.save(storage, player.clone(), &(spinned + spins))?;
+
+    // update wheel's total reward supply
+    WHEEL_REWARDS.save(storage, &(supply - spins, wheel_rewards))?;
+
+    add_spin_stats(storage, &config.price, spins, paid_spins, spinned == 0)?;
+
+    Ok((config, spins, free_spins, spinned, funds))
+}
+
+pub fn spin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    number: Option<u32>,
+    referrer: Option<String>,
+) -> Result<Response, ContractError> {
+    add_referral(deps.api, deps.storage, &info.sender, referrer)?;
+
+    spin_wheel(deps, env, info.sender, info.funds, number, None)
+}
+
+pub fn spin_for(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    player: String,
+    number: Option<u32>,
+    callback: Option<Binary>,
+    referrer: Option<String>,
+) -> Result<Response, ContractError> {
+    // only registered operators can spin for players
+    if !OPERATORS.has(deps.storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let player = addr_validate(deps.api, &player)?;
+    add_referral(deps.api, deps.storage, &player, referrer)?;
+
+    let response = spin_wheel(
+        deps,
+        env,
+        player,
+        info.funds,
+        number,
+        Some(SpinCallback {
+            operator: info.sender.clone(),
+            data: callback,
+        }),
+    )?;
+
+    Ok(response.add_attribute("operator", info.sender))
+}
+
+/// Spin for player, results of spins made by an operator are sent back by `callback`
+fn spin_wheel(
+    deps: DepsMut,
+    env: Env,
+    player: Addr,
+    funds: Vec<Coin>,
+    number: Option<u32>,
+    callback: Option<SpinCallback>,
+) -> Result<Response, ContractError> {
+    // spins paid by an operator do not use free spins of player
+    let payer = callback.as_ref().map(|callback| callback.operator.clone());
+    let (config, spins, free_spins, spinned, funds) =
+        take_spins(deps.storage, &env, &player, funds, number, payer.is_none())?;
+
+    let spin_event = spin_event(&config, &player, spins, free_spins);
+
+    // in basic mode, spins must be committed and revealed if the wheel has a reveal window
+    if !config.is_advanced_randomness
+        && !config.is_commit_reveal
+        && config.spin_reveal_window.is_some()
+    {
+        return Err(ContractError::CustomError {
+            val: "spins must be committed by CommitSpin".to_string(),
+        });
+    }
+
+    if config.is_advanced_randomness {
+        let (job_number, job_id) = next_job_id(deps.storage, &player)?;
+
+        // Make randomness request message to the randomness provider
+        let msg = randomness_request_msg(&config, job_id.clone(), funds)?;
+
+        // spin payments are locked until the randomness arrives or the spin is refunded
+        let payment = checked_u128_mul_u32(config.price.amount, spins - free_spins);
+        if !payment.is_zero() {
+            lock_coin(deps.storage, config.price.denom, payment)?;
+        }
+
+        // save job for mapping callback response to request
+        let random_job = RandomJob {
+            player: player.clone(),
+            spins,
+            entropy: None,
+            submitted_at: env.block.time,
+            free_spins,
+            callback,
+            payer,
+            number: job_number,
+        };
+
+        save_random_job(deps.storage, &job_id, &random_job)?;
+
+        let response = Response::new()
+            .add_attribute("action", "spin")
+            .add_attribute("sender", player.clone())
+            .add_attribute("spins", spins.to_string())
+            .add_attribute("job_id", job_id.clone())
+            .add_event(spin_event.add_attribute("job_id", job_id.clone()))
+            .add_message(msg);
+
+        // rewards are selected when the randomness arrives
+        Ok(add_spin_outcome(
+            response,
+            player,
+            Some(job_id),
+            vec![],
+            None,
+        )?)
+    } else if config.is_commit_reveal {
+        let (job_number, job_id) = next_job_id(deps.storage, &player)?;
+
+        // entropy of this spin, unknown to admin when the seed was committed
+        let tx_index = env.transaction.map(|tx| tx.index).unwrap_or_default();
+        let entropy = hex::encode(Sha256::digest(
+            format!(
+                "{}{}{}{}",
+                job_id, env.block.height, env.block.time, tx_index
+            )
+            .as_bytes(),
+        ));
+
+        // spin payments are locked until the spin is settled or refunded
+        let payment = checked_u128_mul_u32(config.price.amount, spins - free_spins);
+        if !payment.is_zero() {
+            lock_coin(deps.storage, config.price.denom, payment)?;
+        }
+
+        // save job for settling after the seed is revealed
+        let random_job = RandomJob {
+            player: player.clone(),
+            spins,
+            entropy: Some(entropy),
+            submitted_at: env.block.time,
+            free_spins,
+            callback,
+            payer,
+            number: job_number,
+        };
+
+        save_random_job(deps.storage, &job_id, &random_job)?;
+
+        let response = Response::new()
+            .add_attribute("action", "spin")
+            .add_attribute("sender", player.clone())
+            .add_attribute("spins", spins.to_string())
+            .add_attribute("job_id", job_id.clone())
+            .add_event(spin_event.add_attribute("job_id", job_id.clone()));
+
+        // rewards are selected when the spin is settled
+        Ok(add_spin_outcome(
+            response,
+            player,
+            Some(job_id),
+            vec![],
+            None,
+        )?)
+    } else {
+        // load RANDOM_SEED from the storage
+        let random_seed = RANDOM_SEED.load(deps.storage)?;
+
+        // init a key for the random provider from the msg.sender and current time
+        let key = format!("{}{}", player, env.block.time);
+
+        // select rewards for player
+        let (new_random_seed, records) = select_wheel_rewards(
+            deps.storage,
+            &env.block,
+            player.clone(),
+            random_seed,
+            key,
+            spins,
+            None,
+        )?;
+
+        // update new random seed
+        RANDOM_SEED.save(deps.storage, &new_random_seed)?;
+
+        let response = Response::new()
+            .add_attribute("action", "spin")
+            .add_attribute("sender", player.clone())
+            .add_attribute("spun", spinned.to_string())
+            .add_attribute("spins", spins.to_string())
+            .add_event(spin_event);
+
+        Ok(add_spin_outcome(response, player, None, records, callback)?)
+    }
+}
+
+pub fn commit_spin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    number: Option<u32>,
+    commitment: String,
+    referrer: Option<String>,
+) -> Result<Response, ContractError> {
+    // commitment must be sha256 hex string
+    if commitment.len() != 64 || !commitment.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ContractError::CustomError {
+            val: "invalid commitment".to_string(),
+        });
+    }
+
+    // an expired commit of player is replaced by the new one
+    if let Some(spin_commit) = SPIN_COMMITS.may_load(deps.storage, info.sender.clone())? {
+        let config = CONFIG.load(deps.storage)?;
+        if !is_spin_commit_expired(&config, &spin_commit, &env) {
+            return Err(ContractError::CustomError {
+                val: "the previous spin commit is not revealed".to_string(),
+            });
+        }
+        remove_spin_commit(deps.storage, info.sender.clone(), spin_commit)?;
+    }
+
+    add_referral(deps.api, deps.storage, &info.sender, referrer)?;
+
+    let (config, spins, free_spins, _, _) =
+        take_spins(deps.storage, &env, &info.sender, info.funds, number, true)?;
+
+    if config.is_advanced_randomness
+        || config.is_commit_reveal
+        || config.spin_reveal_window.is_none()
+    {
+        return Err(ContractError::CustomError {
+            val: "the wheel does not accept spin commits".to_string(),
+        });
+    }
+
+    // the result only depends on the seed at commit and the reveal block
+    let random_seed = RANDOM_SEED.load(deps.storage)?;
+
+    SPIN_COMMITS.save(
+        deps.storage,
+        info.sender.clone(),
+        &SpinCommit {
+            commitment: commitment.to_lowercase(),
+            spins,
+            height: env.block.height,
+            seed: HexBinary::from(random_seed),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "commit_spin")
+        .add_attribute("sender", info.sender.clone())
+        .add_attribute("spins", spins.to_string())
+        .add_event(spin_event(&config, &info.sender, spins, free_spins)))
+}
+
+pub fn reveal_spin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    secret: String,
+) -> Result<Response, ContractError> {
+    let spin_commit =
+        if let Some(spin_commit) = SPIN_COMMITS.may_load(deps.storage, info.sender.clone())? {
+            spin_commit
+        } else {
+            return Err(ContractError::SpinCommitNotFound {});
+        };
+
+    // the reveal block is fixed at commit, so the player can not choose the block of the result
+    let config = CONFIG.load(deps.storage)?;
+    if is_spin_commit_expired(&config, &spin_commit, &env) {
+        return Err(ContractError::SpinCommitExpired {});
+    }
+
+    let reveal_height = spin_reveal_height(&config, &spin_commit);
+    if env.block.height != reveal_height {
+        return Err(ContractError::CustomError {
+            val: format!("the spin must be revealed at block {}", reveal_height),
+        });
+    }
+
+    if hex::encode(Sha256::digest(secret.as_bytes())) != spin_commit.commitment {
+        return Err(ContractError::CustomError {
+            val: "the secret does not match the commitment".to_string(),
+        });
+    }
+
+    // use RANDOM_SEED snapshot of the commit
+    let random_seed = spin_commit
+        .seed
+        .to_array::<32>()
+        .map_err(|_| ContractError::InvalidSeed {})?;
+
+    // init a key for the random provider from the secret and the reveal block
+    let key = format!("{}{}{}", secret, env.block.height, env.block.time);
+
+    // select rewards for player
+    let (new_random_seed, records) = select_wheel_rewards(
+        deps.storage,
+        &env.block,
+        info.sender.clone(),
+        random_seed,
+        key,
+        spin_commit.spins,
+        None,
+    )?;
+
+    // update new random seed
+    RANDOM_SEED.save(deps.storage, &new_random_seed)?;
+
+    SPIN_COMMITS.remove(deps.storage, info.sender.clone());
+
+    let response = Response::new()
+        .add_attribute("action", "reveal_spin")
+        .add_attribute("sender", info.sender.clone())
+        .add_attribute("spins", spin_commit.spins.to_string());
+
+    Ok(add_spin_outcome(
+        response,
+        info.sender,
+        None,
+        records,
+        None,
+    )?)
+}
+
+pub fn expire_spin_commit(
+    deps: DepsMut,
+    env: Env,
+    player: String,
+) -> Result<Response, ContractError> {
+    let player = Addr::unchecked(player);
+
+    let spin_commit =
+        if let Some(spin_commit) = SPIN_COMMITS.may_load(deps.storage, player.clone())? {
+            spin_commit
+        } else {
+            return Err(ContractError::SpinCommitNotFound {});
+        };
+
+    let config = CONFIG.load(deps.storage)?;
+    if !is_spin_commit_expired(&config, &spin_commit, &env) {
+        return Err(ContractError::SpinCommitNotExpired {});
+    }
+
+    remove_spin_commit(deps.storage, player.clone(), spin_commit)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "expire_spin_commit")
+        .add_attribute("player", player))
+}
+
+fn is_spin_commit_expired(config: &Config, spin_commit: &SpinCommit, env: &Env) -> bool {
+    env.block.height > spin_reveal_height(config, spin_commit)
+}
+
+/// Block height a spin commit must be revealed at
+fn spin_reveal_height(config: &Config, spin_commit: &SpinCommit) -> u64 {
+    let window = config.spin_reveal_window.unwrap_or_default();
+    spin_commit.height.saturating_add(window)
+}
+
+/// Remove an expired spin commit, its rewards are returned to the wheel
+/// but the payment and spin count of player are forfeited
+fn remove_spin_commit(
+    storage: &mut dyn Storage,
+    player: Addr,
+    spin_commit: SpinCommit,
+) -> Result<(), ContractError> {
+    let (supply, wheel_rewards) = WHEEL_REWARDS.load(storage)?;
+    WHEEL_REWARDS.save(storage, &(supply + spin_commit.spins, wheel_rewards))?;
+
+    SPIN_COMMITS.remove(storage, player);
+
+    Ok(())
+}
+
+/// check if there is enough funds
+fn check_funds(funds: &mut Vec<Coin>, spins: u32, config: Config) -> Result<(), ContractError> {
+    let total_amount = checked_u128_mul_u32(config.price.amount, spins);
+    if total_amount.is_zero() {
+        return Ok(());
+    }
+
+    if let Some(coin_idx) = funds.iter().position(|c| c.denom == config.price.denom) {
+        if funds[coin_idx].amount < total_amount {
+            return Err(ContractError::InsufficentFund {});
+        }
+
+        if config.is_advanced_randomness {
+            if funds[coin_idx].amount == total_amount {
+                funds.swap_remove(coin_idx);
+            } else {
+                funds[coin_idx].amount = funds[coin_idx].amount.checked_sub(total_amount).unwrap();
+            }
         }
+    } else {
+        return Err(ContractError::InsufficentFund {});
     }
 
-    if end_time <= env.block.time {
-        return Err(ContractError::WheelEnded {});
-    }
-
-    let mut admin_config: AdminConfig = ADMIN_CONFIG.load(deps.storage)?;
+    Ok(())
+}
 
-    admin_config.activate = true;
-    ADMIN_CONFIG.save(deps.storage, &admin_config)?;
+/// add a share of spin payment to the jackpot pool
+fn add_jackpot_contribution(
+    storage: &mut dyn Storage,
+    price: &Coin,
+    spins: u32,
+) -> Result<(), ContractError> {
+    let mut jackpot = if let Some(jackpot) = JACKPOT.may_load(storage)? {
+        jackpot
+    } else {
+        return Ok(());
+    };
 
-    let mut config = CONFIG.load(deps.storage)?;
-    config.price = price;
-    config.start_time = start_time;
-    config.end_time = Some(end_time);
-    CONFIG.save(deps.storage, &config)?;
+    // only payments in jackpot denom can grow the pool
+    if jackpot.denom != price.denom {
+        return Ok(());
+    }
 
-    let shuffle = shuffle.unwrap_or(false);
-    // if required, shuffle wheel rewards
-    if shuffle {
-        let random_seend = RANDOM_SEED.load(deps.storage)?;
-        let (supply, wheel_rewards) = WHEEL_REWARDS.load(deps.storage)?;
+    let contribution = checked_u128_mul_u32(price.amount, spins) * jackpot.contribution_rate;
 
-        let wheel_rewards_shuffled = nois_shuffle(random_seend, wheel_rewards);
+    if contribution > Uint128::zero() {
+        jackpot.pool = jackpot
+            .pool
+            .checked_add(contribution)
+            .map_err(StdError::from)?;
+        JACKPOT.save(storage, &jackpot)?;
 
-        // save rewards after shuffled
-        WHEEL_REWARDS.save(deps.storage, &(supply, wheel_rewards_shuffled))?;
+        lock_coin(storage, jackpot.denom, contribution)?;
     }
 
-    Ok(Response::new().add_attribute("action", "activate_wheel"))
+    Ok(())
 }
 
-pub fn spin(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    number: Option<u32>,
-) -> Result<Response, ContractError> {
-    let admin_config = ADMIN_CONFIG.load(deps.storage)?;
-    if !admin_config.activate {
-        return Err(ContractError::WheelNotActivated {});
+/// Payment of a fulfilled pending spin is no longer refundable, shares of it go to the jackpot pool and the referrer
+fn release_job_payment(
+    storage: &mut dyn Storage,
+    random_job: &RandomJob,
+) -> Result<(), ContractError> {
+    let config = CONFIG.load(storage)?;
+    let paid_spins = random_job.spins - random_job.free_spins;
+    let payment = checked_u128_mul_u32(config.price.amount, paid_spins);
+    if !payment.is_zero() {
+        unlock_coin(storage, config.price.denom.clone(), payment)?;
     }
 
-    let config = CONFIG.load(deps.storage)?;
+    distribute_payment(storage, &random_job.player, &config.price, paid_spins)
+}
 
-    let spins = number.unwrap_or(1);
-    if spins == 0 || spins > MAX_SPINS_PER_TURN {
-        return Err(ContractError::InvalidNumberSpins {});
-    }
+/// Distribute shares of a final spin payment
+fn distribute_payment(
+    storage: &mut dyn Storage,
+    player: &Addr,
+    price: &Coin,
+    paid_spins: u32,
+) -> Result<(), ContractError> {
+    add_jackpot_contribution(storage, price, paid_spins)?;
+    reward_referrer(storage, player, price, paid_spins)
+}
 
-    // Check if the wheel has enough rewards
-    // In basic random mode, this check is unnecessary because NOIS function `selected_from_weighted` has checkpoint for this situation
-    // But in advanced random mode, we need this to ensure reward always sufficient
-    let (supply, wheel_rewards) = WHEEL_REWARDS.load(deps.storage)?;
-    if supply < spins {
-        return Err(ContractError::InsufficentReward {});
-    }
+/// Save the referrer of a new player, the referral can not be changed later
+fn add_referral(
+    api: &dyn Api,
+    storage: &mut dyn Storage,
+    player: &Addr,
+    referrer: Option<String>,
+) -> Result<(), ContractError> {
+    let referrer = match referrer {
+        Some(referrer) => api.addr_validate(&referrer)?,
+        None => return Ok(()),
+    };
 
-    let spinned_result = WHITELIST.may_load(deps.storage, info.sender.clone())?;
+    // referrer is ignored if player was referred or has already spun
+    if REFERRALS.has(storage, player.clone())
+        || WHITELIST.may_load(storage, player.clone())?.unwrap_or(0) > 0
+    {
+        return Ok(());
+    }
 
-    // If the wheel is private, only the whitelist is allowed to spin
-    if !config.is_public && spinned_result.is_none() {
-        return Err(ContractError::Unauthorized {});
+    if referrer == player {
+        return Err(ContractError::InvalidReferrer {});
     }
 
-    if let Some(start_time) = config.start_time {
-        if start_time > env.block.time {
-            return Err(ContractError::WheelNotStarted {});
+    // player must not be a referrer in the referral chain of referrer
+    let mut upline = referrer.clone();
+    while let Some(referral) = REFERRALS.may_load(storage, upline)? {
+        if referral.referrer == player {
+            return Err(ContractError::InvalidReferrer {});
         }
+        upline = referral.referrer;
     }
 
-    if config.end_time.unwrap() < env.block.time {
-        return Err(ContractError::WheelEnded {});
+    REFERRALS.save(
+        storage,
+        player.clone(),
+        &Referral {
+            referrer,
+            is_rewarded: false,
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Reward the referrer of player on their first paid spins
+fn reward_referrer(
+    storage: &mut dyn Storage,
+    player: &Addr,
+    price: &Coin,
+    paid_spins: u32,
+) -> Result<(), ContractError> {
+    if paid_spins == 0 {
+        return Ok(());
     }
 
-    if spinned_result.is_none() {
-        SPINS_RESULT.save(deps.storage, info.sender.clone(), &Vec::new())?;
+    let mut referral = match REFERRALS.may_load(storage, player.clone())? {
+        Some(referral) if !referral.is_rewarded => referral,
+        _ => return Ok(()),
+    };
+
+    referral.is_rewarded = true;
+    REFERRALS.save(storage, player.clone(), &referral)?;
+
+    match REFERRAL_REWARD.may_load(storage)? {
+        Some(ReferralReward::FeeShare { rate }) => {
+            let share = checked_u128_mul_u32(price.amount, paid_spins) * rate;
+            if !share.is_zero() {
+                // fee share is locked until referrer claims it
+                lock_coin(storage, price.denom.clone(), share)?;
+
+                let mut earnings = REFERRAL_EARNINGS
+                    .may_load(storage, referral.referrer.clone())?
+                    .unwrap_or_default();
+                if let Some(earning) = earnings.iter_mut().find(|c| c.denom == price.denom) {
+                    earning.amount = earning.amount.checked_add(share).unwrap();
+                } else {
+                    earnings.push(Coin::new(share.u128(), price.denom.clone()));
+                }
+                REFERRAL_EARNINGS.save(storage, referral.referrer, &earnings)?;
+            }
+        }
+        Some(ReferralReward::FreeSpins { spins }) => {
+            // more free spins than paid spins of referred player would be farmed by fake referrals
+            let spins = spins.min(paid_spins);
+            FREE_SPINS.update(storage, referral.referrer, |free_spins| -> StdResult<_> {
+                Ok(free_spins.unwrap_or(0) + spins)
+            })?;
+        }
+        None => {}
     }
 
-    let spinned = spinned_result.unwrap_or(0);
+    Ok(())
+}
 
-    // check funds
-    let mut funds = info.funds;
-    check_funds(funds.as_mut(), spins, config.clone())?;
+pub fn settle_spin(deps: DepsMut, env: Env, job_id: String) -> Result<Response, ContractError> {
+    let seed = if let Some(seed) = SEED_COMMITMENT
+        .may_load(deps.storage)?
+        .and_then(|commitment| commitment.seed)
+    {
+        randomness_from_str(seed).map_err(|_| ContractError::InvalidSeed {})?
+    } else {
+        return Err(ContractError::SeedNotRevealed {});
+    };
 
-    if spins > (config.max_spins_per_address - spinned) {
-        return Err(ContractError::CustomError {
-            val: format!(
-                "Too many spins request: {} left",
-                config.max_spins_per_address - spinned
-            ),
-        });
-    }
+    let random_job: RandomJob =
+        if let Some(job) = RANDOM_JOBS.may_load(deps.storage, job_id.clone())? {
+            job
+        } else {
+            return Err(ContractError::RandomJobNotFound {});
+        };
 
-    WHITELIST.save(deps.storage, info.sender.clone(), &(spinned + spins))?;
+    // the outcome depends on the wheel state, so jobs are settled in the order they were made
+    if let Some((_, next_job_id)) = JOB_QUEUE
+        .range(deps.storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?
+    {
+        if next_job_id != job_id {
+            return Err(ContractError::CustomError {
+                val: format!("the job {} must be settled first", next_job_id),
+            });
+        }
+    }
 
-    // update wheel's total reward supply
-    WHEEL_REWARDS.save(deps.storage, &(supply - spins, wheel_rewards))?;
+    // mix the revealed seed with entropy of the spin
+    let mut hasher = Sha256::new();
+    hasher.update(seed);
+    hasher.update(random_job.entropy.clone().unwrap_or_default().as_bytes());
+    let randomness: [u8; 32] = hasher.finalize().into();
 
-    if config.is_advanced_randomness {
-        let job_id = format!("{}/{}", info.sender, spinned);
+    release_job_payment(deps.storage, &random_job)?;
 
-        // Make randomness request message to NOIS proxy contract
-        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: config.nois_proxy.into(),
-            msg: to_json_binary(&ProxyExecuteMsg::GetNextRandomness {
-                job_id: job_id.clone(),
-            })?,
-            funds,
-        });
+    let (_, records) = select_wheel_rewards(
+        deps.storage,
+        &env.block,
+        random_job.player.clone(),
+        randomness,
+        job_id.clone(),
+        random_job.spins,
+        Some(job_id.clone()),
+    )?;
 
-        // save job for mapping callback response to request
-        let random_job = RandomJob {
-            player: info.sender.clone(),
-            spins,
-        };
+    // job finished, just remove
+    remove_random_job(deps.storage, &job_id, &random_job);
 
-        RANDOM_JOBS.save(deps.storage, job_id.clone(), &random_job)?;
+    let response = Response::new()
+        .add_attribute("action", "settle_spin")
+        .add_attribute("job_id", job_id.clone());
 
-        return Ok(Response::new()
-            .add_attribute("action", "spin")
-            .add_attribute("sender", info.sender)
-            .add_attribute("spins", spins.to_string())
-            .add_attribute("job_id", job_id)
-            .add_message(msg));
-    } else {
-        // load RANDOM_SEED from the storage
-        let random_seed = RANDOM_SEED.load(deps.storage)?;
+    Ok(add_spin_outcome(
+        response,
+        random_job.player,
+        Some(job_id),
+        records,
+        random_job.callback,
+    )?)
+}
 
-        // init a key for the random provider from the msg.sender and current time
-        let key = format!("{}{}", info.sender, env.block.time);
+pub fn cancel_random_job(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    job_id: String,
+) -> Result<Response, ContractError> {
+    let random_job: RandomJob =
+        if let Some(job) = RANDOM_JOBS.may_load(deps.storage, job_id.clone())? {
+            job
+        } else {
+            return Err(ContractError::RandomJobNotFound {});
+        };
 
-        // select rewards for player
-        let new_random_seed =
-            select_wheel_rewards(deps.storage, info.sender.clone(), random_seed, key, spins)?;
+    // spins are refunded to the account that paid them
+    let payer = random_job
+        .payer
+        .clone()
+        .unwrap_or(random_job.player.clone());
 
-        // update new random seed
-        RANDOM_SEED.save(deps.storage, &new_random_seed)?;
+    // the player, payer or admin can cancel the job, so that expired jobs do not block withdrawals
+    let admin_config = ADMIN_CONFIG.load(deps.storage)?;
+    if random_job.player != info.sender && payer != info.sender && admin_config.admin != info.sender
+    {
+        return Err(ContractError::Unauthorized {});
+    }
 
-        return Ok(Response::new()
-            .add_attribute("action", "spin")
-            .add_attribute("sender", info.sender)
-            .add_attribute("spun", spinned.to_string())
-            .add_attribute("spins", spins.to_string()));
+    let config = CONFIG.load(deps.storage)?;
+    if config.is_commit_reveal {
+        // spins can only be refunded if the seed is not revealed in time
+        let revealed = SEED_COMMITMENT
+            .may_load(deps.storage)?
+            .is_some_and(|commitment| commitment.seed.is_some());
+        let reveal_timeout = config.reveal_timeout.unwrap_or(DEFAULT_REVEAL_TIMEOUT);
+        if revealed || config.end_time.unwrap().plus_seconds(reveal_timeout) >= env.block.time {
+            return Err(ContractError::RandomJobNotExpired {});
+        }
+    } else if random_job.submitted_at.plus_seconds(RANDOMNESS_TIMEOUT) >= env.block.time {
+        // randomness does not arrive in time
+        return Err(ContractError::RandomJobNotExpired {});
     }
-}
 
-/// check if there is enough funds
-fn check_funds(funds: &mut Vec<Coin>, spins: u32, config: Config) -> Result<(), ContractError> {
-    if config.price.amount == Uint128::zero() {
-        return Ok(());
+    // restore wheel's total reward supply
+    let (supply, wheel_rewards) = WHEEL_REWARDS.load(deps.storage)?;
+    WHEEL_REWARDS.save(deps.storage, &(supply + random_job.spins, wheel_rewards))?;
+
+    // restore player's spin count, the player may have been removed or re-added by admin
+    let spinned = WHITELIST.may_load(deps.storage, random_job.player.clone())?;
+    if let Some(spinned) = spinned {
+        WHITELIST.save(
+            deps.storage,
+            random_job.player.clone(),
+            &spinned.saturating_sub(random_job.spins),
+        )?;
     }
 
-    let total_amount = checked_u128_mul_u32(config.price.amount, spins);
+    let paid_spins = random_job.spins - random_job.free_spins;
+    remove_spin_stats(
+        deps.storage,
+        &config.price,
+        random_job.spins,
+        paid_spins,
+        spinned.is_some_and(|spinned| spinned <= random_job.spins),
+    )?;
 
-    if let Some(coin_idx) = funds.iter().position(|c| c.denom == config.price.denom) {
-        if funds[coin_idx].amount < total_amount {
-            return Err(ContractError::InsufficentFund {});
-        }
+    // restore player's free spins
+    if random_job.free_spins > 0 {
+        FREE_SPINS.update(
+            deps.storage,
+            random_job.player.clone(),
+            |free_spins| -> StdResult<_> { Ok(free_spins.unwrap_or(0) + random_job.free_spins) },
+        )?;
+    }
 
-        if config.is_advanced_randomness {
-            if funds[coin_idx].amount == total_amount {
-                funds.swap_remove(coin_idx);
-            } else {
-                funds[coin_idx].amount = funds[coin_idx].amount.checked_sub(total_amount).unwrap();
-            }
-        }
-    } else {
-        return Err(ContractError::InsufficentFund {});
+    // refund spin payment, reversing the `spin` event
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    let payment = checked_u128_mul_u32(config.price.amount, paid_spins);
+    let refund_event = Event::new("refund")
+        .add_attribute("player", random_job.player.to_string())
+        .add_attribute("job_id", job_id.clone())
+        .add_attribute("spins", random_job.spins.to_string())
+        .add_attribute("free_spins", random_job.free_spins.to_string())
+        .add_attribute(
+            "payment",
+            Coin::new(payment.u128(), config.price.denom.clone()).to_string(),
+        )
+        .add_attribute("recipient", payer.to_string());
+    if !payment.is_zero() {
+        unlock_coin(deps.storage, config.price.denom.clone(), payment)?;
+
+        send_coin_msg(
+            msgs.as_mut(),
+            payer.to_string(),
+            vec![Coin::new(payment.u128(), config.price.denom)],
+        )?;
     }
 
-    return Ok(());
+    remove_random_job(deps.storage, &job_id, &random_job);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_random_job")
+        .add_attribute("job_id", job_id)
+        .add_event(refund_event)
+        .add_messages(msgs))
 }
 
 pub fn claim_reward(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     rewards: Vec<u32>,
 ) -> Result<Response, ContractError> {
@@ -571,45 +2460,249 @@ pub fn claim_reward(
         return Err(ContractError::WheelNotActivated {});
     }
 
-    let mut spins_result =
-        if let Some(result) = SPINS_RESULT.may_load(deps.storage, info.sender.clone())? {
-            result
-        } else {
-            return Err(ContractError::PlayerNotFound {});
-        };
+    if !is_player(deps.storage, &info.sender) {
+        return Err(ContractError::PlayerNotFound {});
+    }
 
     let mut msgs: Vec<CosmosMsg> = Vec::new();
+    let mut events: Vec<Event> = Vec::new();
 
     for idx in rewards {
-        if let Some((is_claimed, reward)) = spins_result.get(idx as usize) {
-            if !is_claimed {
-                withdraw_reward_msgs(
+        let key = (info.sender.clone(), idx);
+        if let Some(mut record) = spin_records().may_load(deps.storage, key.clone())? {
+            // vouchers are only settled by `RedeemVoucher`
+            let is_voucher = matches!(record.reward, WheelReward::Voucher(_));
+            if !record.is_claimed && !is_voucher {
+                events.push(
+                    Event::new("claim")
+                        .add_attribute("player", info.sender.to_string())
+                        .add_attribute("index", idx.to_string())
+                        .add_attributes(reward_attributes(&record.reward)?)
+                        .add_attribute("recipient", info.sender.to_string()),
+                );
+
+                claim_reward_msgs(
                     deps.storage,
-                    reward.to_owned(),
+                    record.reward.clone(),
                     info.sender.to_string(),
                     msgs.as_mut(),
                 )?;
 
-                spins_result[idx as usize].0 = true;
+                add_claimed_stats(deps.storage, record.reward.get_id())?;
+
+                // update player reward
+                record.is_claimed = true;
+                record.claimed_at = Some(env.block.time);
+                record.claim_recipient = Some(info.sender.clone());
+                spin_records().save(deps.storage, key, &record)?;
             }
         }
     }
 
-    // update player reward
-    SPINS_RESULT.save(deps.storage, info.sender.clone(), &spins_result)?;
-
-    if msgs.len() > 0 {
+    if !msgs.is_empty() {
         Ok(Response::new()
             .add_attribute("action", "claim_reward")
             .add_attribute("sender", info.sender)
+            .add_events(events)
             .add_messages(msgs))
     } else {
         Ok(Response::new()
             .add_attribute("action", "claim_reward")
-            .add_attribute("sender", info.sender))
+            .add_attribute("sender", info.sender)
+            .add_events(events))
     }
 }
 
+pub fn redeem_voucher(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    player: String,
+    reward: u32,
+    code: String,
+) -> Result<Response, ContractError> {
+    // only admin or voucher oracle can attest redemptions
+    let admin_config = ADMIN_CONFIG.load(deps.storage)?;
+    if admin_config.admin != info.sender
+        && VOUCHER_ORACLE.may_load(deps.storage)? != Some(info.sender.clone())
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let player = addr_validate(deps.api, &player)?;
+
+    if !is_player(deps.storage, &player) {
+        return Err(ContractError::PlayerNotFound {});
+    }
+
+    let mut record =
+        if let Some(record) = spin_records().may_load(deps.storage, (player.clone(), reward))? {
+            record
+        } else {
+            return Err(ContractError::InvalidSlotReward {});
+        };
+    let voucher = match &record.reward {
+        WheelReward::Voucher(voucher) => voucher.clone(),
+        _ => return Err(ContractError::InvalidSlotReward {}),
+    };
+
+    if VOUCHER_REDEMPTIONS.has(deps.storage, (voucher.id, voucher.next_index)) {
+        return Err(ContractError::VoucherAlreadyRedeemed {});
+    }
+
+    // a voucher claimed by `ClaimReward` can not be redeemed anymore
+    if record.is_claimed {
+        return Err(ContractError::CustomError {
+            val: "the voucher is already claimed".to_string(),
+        });
+    }
+
+    // revealed code must match the committed hash
+    if hex::encode(Sha256::digest(code.as_bytes())) != voucher.code_hashes[0] {
+        return Err(ContractError::InvalidCodeHash {});
+    }
+
+    VOUCHER_REDEMPTIONS.save(
+        deps.storage,
+        (voucher.id, voucher.next_index),
+        &VoucherRedemption {
+            player: player.clone(),
+            code,
+            attested_by: info.sender.clone(),
+            redeemed_at: env.block.time,
+        },
+    )?;
+
+    let claim_event = Event::new("claim")
+        .add_attribute("player", player.to_string())
+        .add_attribute("index", reward.to_string())
+        .add_attributes(reward_attributes(&WheelReward::Voucher(voucher.clone()))?)
+        .add_attribute("recipient", player.to_string());
+
+    // redeemed voucher is considered claimed
+    add_claimed_stats(deps.storage, voucher.id)?;
+    record.is_claimed = true;
+    record.claimed_at = Some(env.block.time);
+    record.claim_recipient = Some(player.clone());
+    spin_records().save(deps.storage, (player.clone(), reward), &record)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "redeem_voucher")
+        .add_attribute("player", player)
+        .add_attribute("slot_id", voucher.id.to_string())
+        .add_attribute("index", voucher.next_index.to_string())
+        .add_event(claim_event))
+}
+
+pub fn claim_vested(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let vestings = if let Some(vestings) = VESTINGS.may_load(deps.storage, info.sender.clone())? {
+        vestings
+    } else {
+        return Err(ContractError::PlayerNotFound {});
+    };
+
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    let mut events: Vec<Event> = Vec::new();
+    let mut remaining: Vec<VestingRecord> = Vec::with_capacity(vestings.len());
+
+    for mut vesting in vestings {
+        let releasable = vesting
+            .vested(env.block.time)
+            .checked_sub(vesting.released)
+            .unwrap();
+
+        if !releasable.is_zero() {
+            let (kind, asset) = match &vesting.asset {
+                VestingAsset::Coin(coin) => ("coin", coin.denom.clone()),
+                VestingAsset::Token(token) => ("fungible_token", token.token_address.clone()),
+            };
+            events.push(
+                Event::new("vesting_release")
+                    .add_attribute("player", info.sender.to_string())
+                    .add_attribute("label", vesting.label.clone())
+                    .add_attribute("kind", kind)
+                    .add_attribute("asset", asset)
+                    .add_attribute("amount", releasable)
+                    .add_attribute("recipient", info.sender.to_string()),
+            );
+
+            match &vesting.asset {
+                VestingAsset::Coin(coin) => {
+                    // remove locked amount
+                    unlock_coin(deps.storage, coin.denom.clone(), releasable)?;
+
+                    send_coin_msg(
+                        msgs.as_mut(),
+                        info.sender.to_string(),
+                        vec![Coin::new(releasable.u128(), coin.denom.clone())],
+                    )?;
+                }
+                VestingAsset::Token(token) => {
+                    transfer_token_msg(
+                        msgs.as_mut(),
+                        info.sender.to_string(),
+                        token.token_address.clone(),
+                        releasable,
+                    )?;
+                }
+            }
+
+            vesting.released += releasable;
+        }
+
+        // fully released vestings are removed
+        if vesting.released < vesting.asset.amount() {
+            remaining.push(vesting);
+        }
+    }
+
+    VESTINGS.save(deps.storage, info.sender.clone(), &remaining)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_vested")
+        .add_attribute("sender", info.sender)
+        .add_events(events)
+        .add_messages(msgs))
+}
+
+pub fn claim_referral_earnings(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let earnings =
+        if let Some(earnings) = REFERRAL_EARNINGS.may_load(deps.storage, info.sender.clone())? {
+            earnings
+        } else {
+            return Err(ContractError::CustomError {
+                val: "no referral earnings".to_string(),
+            });
+        };
+
+    let mut events: Vec<Event> = Vec::with_capacity(earnings.len());
+    for earning in earnings.iter() {
+        // remove locked amount
+        unlock_coin(deps.storage, earning.denom.clone(), earning.amount)?;
+
+        events.push(
+            Event::new("referral_claim")
+                .add_attribute("referrer", info.sender.to_string())
+                .add_attribute("asset", earning.denom.clone())
+                .add_attribute("amount", earning.amount),
+        );
+    }
+
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    send_coin_msg(msgs.as_mut(), info.sender.to_string(), earnings)?;
+
+    REFERRAL_EARNINGS.remove(deps.storage, info.sender.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_referral_earnings")
+        .add_attribute("sender", info.sender)
+        .add_events(events)
+        .add_messages(msgs))
+}
+
 pub fn withdraw(
     deps: DepsMut,
     env: Env,
@@ -626,6 +2719,11 @@ pub fn withdraw(
         return Err(ContractError::WheelNotEnded {});
     }
 
+    // pending spins are settled with the remaining rewards
+    if has_pending_spins(deps.storage) {
+        return Err(ContractError::PendingSpins {});
+    }
+
     // list rewards of the wheel
     let (supply, mut wheel_rewards) = WHEEL_REWARDS.load(deps.storage)?;
 
@@ -640,21 +2738,28 @@ pub fn withdraw(
     let recipient = recipient.unwrap_or(info.sender.to_string());
     addr_validate(deps.api, &recipient)?;
 
+    let withdraw_event = Event::new("withdraw")
+        .add_attribute("admin", info.sender.to_string())
+        .add_attributes(reward_attributes(&reward)?)
+        .add_attribute("recipient", recipient.clone());
+
     let mut msgs: Vec<CosmosMsg> = Vec::new();
     let removed_supply = withdraw_reward_msgs(deps.storage, reward, recipient, msgs.as_mut())?;
 
     // update wheel rewards
     WHEEL_REWARDS.save(deps.storage, &(supply - removed_supply, wheel_rewards))?;
 
-    if msgs.len() > 0 {
-        return Ok(Response::new()
+    if !msgs.is_empty() {
+        Ok(Response::new()
             .add_attribute("action", "withdraw")
             .add_attribute("slot", slot.to_string())
-            .add_messages(msgs));
+            .add_event(withdraw_event)
+            .add_messages(msgs))
     } else {
-        return Ok(Response::new()
+        Ok(Response::new()
             .add_attribute("action", "withdraw")
-            .add_attribute("slot", slot.to_string()));
+            .add_attribute("slot", slot.to_string())
+            .add_event(withdraw_event))
     }
 }
 
@@ -674,6 +2779,14 @@ pub fn withdraw_coin(
         return Err(ContractError::WheelNotEnded {});
     }
 
+    // pending spins may still win the jackpot or be refunded
+    if has_pending_spins(deps.storage) {
+        return Err(ContractError::PendingSpins {});
+    }
+
+    // the jackpot can not be won anymore, its pool and reserve are released
+    release_jackpot(deps.storage, &denom)?;
+
     // get the balance of contract
     let contract_balance: BalanceResponse =
         deps.querier.query(&QueryRequest::Bank(BankQuery::Balance {
@@ -704,6 +2817,13 @@ pub fn withdraw_coin(
             .unwrap(),
     };
 
+    let withdraw_event = Event::new("withdraw")
+        .add_attribute("admin", info.sender.to_string())
+        .add_attribute("kind", "coin")
+        .add_attribute("asset", coin.denom.clone())
+        .add_attribute("amount", coin.amount)
+        .add_attribute("recipient", recipient.clone());
+
     let mut msgs: Vec<CosmosMsg> = Vec::new();
     send_coin_msg(msgs.as_mut(), recipient.clone(), vec![coin])?;
 
@@ -711,9 +2831,42 @@ pub fn withdraw_coin(
         .add_attribute("action", "withdraw_coin")
         .add_attribute("denom", denom)
         .add_attribute("receiver", recipient)
+        .add_event(withdraw_event)
         .add_messages(msgs))
 }
 
+/// Make message requesting randomness for a job from the configured provider
+fn randomness_request_msg(
+    config: &Config,
+    job_id: String,
+    funds: Vec<Coin>,
+) -> Result<CosmosMsg, ContractError> {
+    let (contract_addr, msg) = match &config.randomness_provider {
+        RandomnessProvider::Nois {} => (
+            config
+                .nois_proxy
+                .as_ref()
+                .ok_or(ContractError::InvalidRandomness {})?
+                .to_string(),
+            to_json_binary(&ProxyExecuteMsg::GetNextRandomness { job_id })?,
+        ),
+        RandomnessProvider::Vrf { oracle } => (
+            oracle.to_string(),
+            to_json_binary(&VrfExecuteMsg::RequestRandomness { request_id: job_id })?,
+        ),
+        RandomnessProvider::Mock { oracle } => (
+            oracle.to_string(),
+            to_json_binary(&MockExecuteMsg::RequestRandomness { job_id })?,
+        ),
+    };
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr,
+        msg,
+        funds,
+    }))
+}
+
 pub fn nois_receive(
     deps: DepsMut,
     env: Env,
@@ -722,15 +2875,72 @@ pub fn nois_receive(
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
-    ensure_eq!(
-        info.sender,
-        config.nois_proxy,
+    // only the configured nois proxy can deliver randomness
+    ensure!(
+        config.randomness_provider == RandomnessProvider::Nois {}
+            && config.nois_proxy == Some(info.sender),
+        ContractError::Unauthorized {}
+    );
+
+    fulfill_random_job(
+        deps,
+        env,
+        callback.job_id,
+        callback.randomness,
+        "nois_receive",
+    )
+}
+
+pub fn vrf_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    callback: VrfCallback,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // only the configured vrf oracle can deliver randomness
+    ensure!(
+        matches!(&config.randomness_provider, RandomnessProvider::Vrf { oracle } if *oracle == info.sender),
+        ContractError::Unauthorized {}
+    );
+
+    fulfill_random_job(
+        deps,
+        env,
+        callback.request_id,
+        callback.randomness,
+        "vrf_receive",
+    )
+}
+
+pub fn mock_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    job_id: String,
+    randomness: HexBinary,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // only the configured mock oracle can deliver randomness
+    ensure!(
+        matches!(&config.randomness_provider, RandomnessProvider::Mock { oracle } if *oracle == info.sender),
         ContractError::Unauthorized {}
     );
 
-    let job_id = callback.job_id;
-    let randomness: [u8; 32] = callback
-        .randomness
+    fulfill_random_job(deps, env, job_id, randomness, "mock_receive")
+}
+
+/// Select rewards of a pending job with randomness delivered by the provider
+fn fulfill_random_job(
+    deps: DepsMut,
+    env: Env,
+    job_id: String,
+    randomness: HexBinary,
+    action: &str,
+) -> Result<Response, ContractError> {
+    let randomness: [u8; 32] = randomness
         .to_array()
         .map_err(|_| ContractError::InvalidRandomness {})?;
 
@@ -741,23 +2951,35 @@ pub fn nois_receive(
             return Err(ContractError::RandomJobNotFound {});
         };
 
+    release_job_payment(deps.storage, &random_job)?;
+
     // init a key for the random provider from the job id and current time
     let key = format!("{}{}", job_id.clone(), env.block.time);
 
-    select_wheel_rewards(
+    let (_, records) = select_wheel_rewards(
         deps.storage,
-        random_job.player,
+        &env.block,
+        random_job.player.clone(),
         randomness,
         key,
         random_job.spins,
+        Some(job_id.clone()),
     )?;
 
     // job finished, just remove
-    RANDOM_JOBS.remove(deps.storage, job_id.clone());
+    remove_random_job(deps.storage, &job_id, &random_job);
 
-    Ok(Response::new()
-        .add_attribute("action", "nois_receive")
-        .add_attribute("job_id", job_id))
+    let response = Response::new()
+        .add_attribute("action", action)
+        .add_attribute("job_id", job_id.clone());
+
+    Ok(add_spin_outcome(
+        response,
+        random_job.player,
+        Some(job_id),
+        records,
+        random_job.callback,
+    )?)
 }
 
 /// validate string if it is valid bench32 string addresss
@@ -773,7 +2995,7 @@ fn addr_validate(api: &dyn Api, addr: &str) -> Result<Addr, ContractError> {
 fn checked_add_supply(supply: u32, inc: u32) -> Result<u32, ContractError> {
     supply
         .checked_add(inc)
-        .ok_or_else(|| ContractError::TooManyRewards {})
+        .ok_or(ContractError::TooManyRewards {})
 }
 
 fn checked_u128_mul_u32(a: Uint128, b: u32) -> Uint128 {
@@ -808,39 +3030,105 @@ fn is_activate_and_owned(storage: &dyn Storage, sender: Addr) -> Result<(), Cont
 
 fn select_wheel_rewards(
     storage: &mut dyn Storage,
+    block: &BlockInfo,
     player: Addr,
     random_seed: [u8; 32],
     key: String,
     spins: u32,
-) -> Result<[u8; 32], ContractError> {
+    job_id: Option<String>,
+) -> Result<([u8; 32], Vec<SpinRecord>), ContractError> {
+    let now = block.time;
+
     let (supply, mut wheel_rewards) = WHEEL_REWARDS.load(storage)?;
 
-    let mut spins_result = SPINS_RESULT.load(storage, player.clone())?;
+    // new results of player are (is_claimed, reward) from the next result index
+    let mut spins_result: Vec<(bool, WheelReward)> = Vec::with_capacity(spins as usize);
+    let first_result = spin_records()
+        .prefix(player.clone())
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |idx| idx + 1);
+
+    let config = CONFIG.load(storage)?;
+    let pity_threshold = config.pity_threshold;
+    let mut losing_streak = LOSING_STREAKS
+        .may_load(storage, player.clone())?
+        .unwrap_or(0);
+
+    let mut jackpot = JACKPOT.may_load(storage)?;
+
+    let tiers = TIERS.may_load(storage)?.unwrap_or_default();
 
-    // generate weighted list for wheel rewards
-    let mut list_weighted: Vec<(usize, u32)> = Vec::with_capacity(wheel_rewards.len());
-    for idx in 0..wheel_rewards.len() {
-        let reward_supply = wheel_rewards[idx].get_supply();
+    // generate weighted list of (slot index, weight, tier index) for wheel rewards
+    let mut list_weighted: Vec<(u32, u32, Option<u32>)> = Vec::with_capacity(wheel_rewards.len());
+    for (idx, reward) in wheel_rewards.iter().enumerate() {
+        let reward_supply = reward.get_supply();
 
         if reward_supply >= 1 {
-            list_weighted.push((idx, reward_supply));
+            let tier_idx = tiers
+                .iter()
+                .position(|tier| reward.get_tier().as_ref() == Some(&tier.name))
+                .map(|tier_idx| tier_idx as u32);
+            list_weighted.push((idx as u32, reward_supply, tier_idx));
         }
     }
 
     // define random provider from the random_seed
-    let mut provider = sub_randomness_with_key(random_seed, key);
+    let mut provider = sub_randomness_with_key(random_seed, key.clone());
 
     let mut randomness = [0u8; 32];
 
-    for _ in 0..spins {
+    for nonce in 0..spins {
         // random a new randomness
         randomness = provider.provide();
 
+        // after too many losing spins in a row, only winning slots can be selected
+        let pity_weighted: Vec<(u32, u32, Option<u32>)> = match pity_threshold {
+            Some(threshold) if losing_streak >= threshold => list_weighted
+                .iter()
+                .filter(|&&(idx, _, _)| !wheel_rewards[idx as usize].is_losing())
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        };
+        let weights = if !pity_weighted.is_empty() {
+            pity_weighted
+        } else {
+            list_weighted.clone()
+        };
+
         // randomly selecting an element from a weighted list
-        let slot_idx: usize = select_from_weighted(randomness, &list_weighted).unwrap();
+        let (slot_idx, tier_idx) = select_slot(randomness, &weights, &tiers)?;
+
+        // record how the slot is selected for the next spin result
+        SPIN_PROOFS.save(
+            storage,
+            (player.clone(), first_result + spins_result.len() as u32),
+            &SpinProof {
+                seed: HexBinary::from(random_seed),
+                key: key.clone(),
+                nonce,
+                randomness: HexBinary::from(randomness),
+                slot_index: slot_idx,
+                tier_index: tier_idx,
+                weights_hash: weights_hash(&weights)?,
+            },
+        )?;
+        let slot_idx = slot_idx as usize;
+
+        // update player's losing streak
+        if wheel_rewards[slot_idx].is_losing() {
+            losing_streak += 1;
+        } else {
+            losing_streak = 0;
+        }
 
         // update weighted
-        let wl_idx = list_weighted.iter().position(|&a| a.0 == slot_idx).unwrap();
+        let wl_idx = list_weighted
+            .iter()
+            .position(|&a| a.0 as usize == slot_idx)
+            .unwrap();
         if list_weighted[wl_idx].1 > 1 {
             list_weighted[wl_idx].1 -= 1;
         } else {
@@ -853,68 +3141,657 @@ fn select_wheel_rewards(
                 // get random nft in collection
                 let id_idx = int_in_range(randomness, 0, collection.token_ids.len() - 1);
 
-                // spin result with nft of index id_idx as reward
-                let reward = WheelReward::NftCollection(CollectionReward {
-                    label: collection.label.clone(),
-                    collection_address: collection.collection_address.clone(),
-                    token_ids: vec![collection.token_ids.swap_remove(id_idx)],
-                    id: collection.id.clone(),
-                });
+                // spin result with nft of index id_idx as reward
+                let reward = WheelReward::NftCollection(CollectionReward {
+                    label: collection.label.clone(),
+                    collection_address: collection.collection_address.clone(),
+                    token_ids: vec![collection.token_ids.swap_remove(id_idx)],
+                    id: collection.id,
+                    tier: collection.tier.clone(),
+                });
+
+                spins_result.push((false, reward));
+            }
+
+            WheelReward::FungibleToken(ref mut token) => {
+                // spin result with token as reward
+                let reward = WheelReward::FungibleToken(TokenReward {
+                    label: token.label.clone(),
+                    token_address: token.token_address.clone(),
+                    amount: token.amount,
+                    number: 1,
+                    id: token.id,
+                    tier: token.tier.clone(),
+                    vesting: token.vesting.clone(),
+                });
+
+                token.number -= 1;
+
+                // won tokens with vesting schedule are delivered by `claim_vested`
+                if let Some(vesting) = &token.vesting {
+                    add_vesting(
+                        storage,
+                        player.clone(),
+                        now,
+                        token.label.clone(),
+                        VestingAsset::Token(TokenAsset {
+                            token_address: token.token_address.clone(),
+                            amount: token.amount,
+                        }),
+                        vesting,
+                    )?;
+
+                    spins_result.push((true, reward));
+                } else {
+                    spins_result.push((false, reward));
+                }
+            }
+
+            WheelReward::Coin(ref mut coin) => {
+                // spin result with coin as reward
+                let reward = WheelReward::Coin(CoinReward {
+                    label: coin.label.clone(),
+                    coin: coin.coin.clone(),
+                    number: 1,
+                    id: coin.id,
+                    tier: coin.tier.clone(),
+                    vesting: coin.vesting.clone(),
+                });
+
+                coin.number -= 1;
+
+                // won coins with vesting schedule are delivered by `claim_vested`
+                if let Some(vesting) = &coin.vesting {
+                    add_vesting(
+                        storage,
+                        player.clone(),
+                        now,
+                        coin.label.clone(),
+                        VestingAsset::Coin(coin.coin.clone()),
+                        vesting,
+                    )?;
+
+                    spins_result.push((true, reward));
+                } else {
+                    spins_result.push((false, reward));
+                }
+            }
+
+            WheelReward::Text(ref mut text) => {
+                // spin result with text as reward
+                let reward = WheelReward::Text(TextReward {
+                    label: text.label.clone(),
+                    number: 1,
+                    id: text.id,
+                    tier: text.tier.clone(),
+                    is_losing: text.is_losing,
+                });
+
+                text.number -= 1;
+
+                spins_result.push((false, reward));
+            }
+
+            WheelReward::RandomCoin(ref mut coin) => {
+                let amount = draw_amount(randomness, coin.min, coin.max, coin.pool, coin.number);
+
+                // spin result with drawn amount of coin as reward
+                let reward = WheelReward::RandomCoin(RandomCoinReward {
+                    label: coin.label.clone(),
+                    denom: coin.denom.clone(),
+                    min: coin.min,
+                    max: coin.max,
+                    pool: Uint128::zero(),
+                    amount: Some(amount),
+                    number: 1,
+                    id: coin.id,
+                    tier: coin.tier.clone(),
+                });
+
+                coin.pool = coin.pool.checked_sub(amount).unwrap();
+                coin.number -= 1;
+
+                spins_result.push((false, reward));
+            }
+
+            WheelReward::RandomToken(ref mut token) => {
+                let amount =
+                    draw_amount(randomness, token.min, token.max, token.pool, token.number);
+
+                // spin result with drawn amount of token as reward
+                let reward = WheelReward::RandomToken(RandomTokenReward {
+                    label: token.label.clone(),
+                    token_address: token.token_address.clone(),
+                    min: token.min,
+                    max: token.max,
+                    pool: Uint128::zero(),
+                    amount: Some(amount),
+                    number: 1,
+                    id: token.id,
+                    tier: token.tier.clone(),
+                });
+
+                token.pool = token.pool.checked_sub(amount).unwrap();
+                token.number -= 1;
+
+                spins_result.push((false, reward));
+            }
+
+            WheelReward::MintNft(ref mut nft) => {
+                // spin result with the next serial nft as reward
+                let reward = WheelReward::MintNft(MintNftReward {
+                    label: nft.label.clone(),
+                    collection_address: nft.collection_address.clone(),
+                    token_id_prefix: nft.token_id_prefix.clone(),
+                    token_uri: nft.token_uri.clone(),
+                    next_serial: nft.next_serial,
+                    number: 1,
+                    id: nft.id,
+                    tier: nft.tier.clone(),
+                });
+
+                nft.next_serial += 1;
+                nft.number -= 1;
+
+                spins_result.push((false, reward));
+            }
+
+            WheelReward::ContractCall(ref mut call) => {
+                // spin result with contract call as reward
+                let reward = WheelReward::ContractCall(ContractCallReward {
+                    label: call.label.clone(),
+                    contract_address: call.contract_address.clone(),
+                    msg: call.msg.clone(),
+                    number: 1,
+                    id: call.id,
+                    tier: call.tier.clone(),
+                });
+
+                call.number -= 1;
+
+                spins_result.push((false, reward));
+            }
+
+            WheelReward::Voucher(ref mut voucher) => {
+                // spin result with the next code as reward
+                let reward = WheelReward::Voucher(VoucherReward {
+                    label: voucher.label.clone(),
+                    code_hashes: vec![voucher.code_hashes.remove(0)],
+                    next_index: voucher.next_index,
+                    id: voucher.id,
+                    tier: voucher.tier.clone(),
+                });
+
+                voucher.next_index += 1;
+
+                spins_result.push((false, reward));
+            }
+
+            WheelReward::Bundle(ref mut bundle) => {
+                // get random bundle in slot
+                let bundle_idx = int_in_range(randomness, 0, bundle.bundles.len() - 1);
+
+                // spin result with bundle of index bundle_idx as reward
+                let reward = WheelReward::Bundle(BundleReward {
+                    label: bundle.label.clone(),
+                    bundles: vec![bundle.bundles.swap_remove(bundle_idx)],
+                    id: bundle.id,
+                    tier: bundle.tier.clone(),
+                });
+
+                spins_result.push((false, reward));
+            }
+        }
+
+        // roll for the jackpot with its own randomness
+        if let Some(ref mut jackpot) = jackpot {
+            let jackpot_randomness = sub_randomness_with_key(randomness, "jackpot").provide();
+
+            if !jackpot.pool.is_zero() && random_decimal(jackpot_randomness) < jackpot.probability {
+                spins_result.push((false, win_jackpot(jackpot)));
+
+                losing_streak = 0;
+            }
+        }
+    }
+
+    if let Some(jackpot) = jackpot {
+        JACKPOT.save(storage, &jackpot)?;
+    }
+
+    let randomness_mode = if config.is_advanced_randomness {
+        RandomnessMode::Advanced
+    } else if config.is_commit_reveal {
+        RandomnessMode::CommitReveal
+    } else if config.spin_reveal_window.is_some() {
+        RandomnessMode::CommitSpin
+    } else {
+        RandomnessMode::Basic
+    };
+
+    // save spins result
+    let mut spin_count = SPIN_COUNT.may_load(storage)?.unwrap_or(0);
+    let mut won_value = Uint128::zero();
+    let mut records: Vec<SpinRecord> = Vec::with_capacity(spins_result.len());
+    for (idx, (is_claimed, reward)) in spins_result.into_iter().enumerate() {
+        spin_count += 1;
+
+        add_won_stats(storage, reward.get_id(), is_claimed)?;
+
+        if let Some(value) = SLOT_VALUES.may_load(storage, reward.get_id())? {
+            won_value = won_value.checked_add(value).unwrap();
+        }
+
+        let index = first_result + idx as u32;
+        let record = SpinRecord {
+            spin_id: spin_count,
+            player: player.clone(),
+            index,
+            reward,
+            is_claimed,
+            height: block.height,
+            time: now,
+            price: config.price.clone(),
+            randomness_mode: randomness_mode.clone(),
+            job_id: job_id.clone(),
+            // rewards claimed when won are delivered to player by vesting
+            claimed_at: is_claimed.then_some(now),
+            claim_recipient: is_claimed.then(|| player.clone()),
+        };
+
+        spin_records().save(storage, (player.clone(), index), &record)?;
+        records.push(record);
+    }
+    SPIN_COUNT.save(storage, &spin_count)?;
+
+    // update player's leaderboard score
+    let mut score = player_scores()
+        .may_load(storage, player.clone())?
+        .unwrap_or(PlayerScore {
+            player: player.clone(),
+            value: Uint128::zero(),
+            spins: 0,
+        });
+    score.value = score.value.checked_add(won_value).unwrap();
+    score.spins += spins;
+    player_scores().save(storage, player.clone(), &score)?;
+
+    // update player's losing streak
+    LOSING_STREAKS.save(storage, player, &losing_streak)?;
+
+    // update wheel rewards
+    WHEEL_REWARDS.save(storage, &(supply, wheel_rewards))?;
+
+    Ok((randomness, records))
+}
+
+/// Add events of won rewards and the spin outcome as data to the response,
+/// the outcome is also sent to the operator who made the spins
+fn add_spin_outcome(
+    response: Response,
+    player: Addr,
+    job_id: Option<String>,
+    records: Vec<SpinRecord>,
+    callback: Option<SpinCallback>,
+) -> StdResult<Response> {
+    let mut events: Vec<Event> = Vec::with_capacity(records.len());
+    let mut rewards: Vec<(u32, WheelReward)> = Vec::with_capacity(records.len());
+    for record in records {
+        let mut event = Event::new("reward_won")
+            .add_attribute("player", record.player.to_string())
+            .add_attribute("spin_id", record.spin_id.to_string())
+            .add_attribute("index", record.index.to_string())
+            .add_attributes(reward_attributes(&record.reward)?);
+        if let Some(job_id) = &record.job_id {
+            event = event.add_attribute("job_id", job_id);
+        }
+        events.push(event);
+
+        rewards.push((record.index, record.reward));
+    }
+
+    let result = SpinResponse {
+        player,
+        job_id,
+        rewards,
+    };
+
+    let mut response = response.add_events(events);
+    if let Some(callback) = callback {
+        // the operator can not reject the outcome by failing the callback
+        let callback_msg = WasmMsg::Execute {
+            contract_addr: callback.operator.to_string(),
+            msg: to_json_binary(&OperatorExecuteMsg::SpinCallback {
+                result: result.clone(),
+                data: callback.data,
+            })?,
+            funds: vec![],
+        };
+        response = response.add_submessage(
+            SubMsg::reply_on_error(callback_msg, SPIN_CALLBACK_REPLY_ID)
+                .with_gas_limit(SPIN_CALLBACK_GAS_LIMIT),
+        );
+    }
+
+    Ok(response.set_data(to_json_binary(&result)?))
+}
+
+/// Event of spins paid by player
+fn spin_event(config: &Config, player: &Addr, spins: u32, free_spins: u32) -> Event {
+    let payment = Coin {
+        denom: config.price.denom.clone(),
+        amount: checked_u128_mul_u32(config.price.amount, spins - free_spins),
+    };
+
+    Event::new("spin")
+        .add_attribute("player", player.to_string())
+        .add_attribute("spins", spins.to_string())
+        .add_attribute("free_spins", free_spins.to_string())
+        .add_attribute("payment", payment.to_string())
+}
+
+/// Attributes of a reward in events
+fn reward_attributes(reward: &WheelReward) -> StdResult<Vec<Attribute>> {
+    let mut attributes = vec![
+        attr("slot_id", reward.get_id().to_string()),
+        attr("label", reward.get_label()),
+        attr("kind", reward.get_kind()),
+    ];
+
+    // an `asset` and `amount` pair for each asset of the reward
+    for (asset, amount) in reward.get_assets()? {
+        attributes.push(attr("asset", asset));
+        attributes.push(attr("amount", amount));
+    }
+
+    Ok(attributes)
+}
+
+/// Start a vesting schedule of won amount for player
+fn add_vesting(
+    storage: &mut dyn Storage,
+    player: Addr,
+    now: Timestamp,
+    label: String,
+    asset: VestingAsset,
+    vesting: &VestingSchedule,
+) -> Result<(), ContractError> {
+    let mut vestings = VESTINGS
+        .may_load(storage, player.clone())?
+        .unwrap_or_default();
+
+    vestings.push(VestingRecord {
+        label,
+        asset,
+        released: Uint128::zero(),
+        start: now,
+        cliff: vesting.cliff,
+        duration: vesting.duration,
+    });
+
+    VESTINGS.save(storage, player, &vestings)?;
+
+    Ok(())
+}
+
+/// Draw a random amount in range [min, max] for one of the `number` items left in the pool.
+/// The amount is capped so that the pool can still pay min amount for the other items
+fn draw_amount(
+    randomness: [u8; 32],
+    min: Uint128,
+    max: Uint128,
+    pool: Uint128,
+    number: u32,
+) -> Uint128 {
+    let reserved = checked_u128_mul_u32(min, number - 1);
+    let upper = max.min(pool.checked_sub(reserved).unwrap());
+
+    // use another randomness for drawing amount
+    let amount_randomness = sub_randomness_with_key(randomness, "amount").provide();
+
+    Uint128::from(int_in_range(amount_randomness, min.u128(), upper.u128()))
+}
+
+/// Select a slot from the weighted list.
+/// If the wheel has tiers, a tier is picked by its weight first, then a slot inside it by supply
+fn select_slot(
+    randomness: [u8; 32],
+    list_weighted: &[(u32, u32, Option<u32>)],
+    tiers: &[Tier],
+) -> StdResult<(u32, Option<u32>)> {
+    // only tiers that still have rewards can be picked
+    let tier_weighted: Vec<(u32, u32)> = tiers
+        .iter()
+        .enumerate()
+        .map(|(tier_idx, tier)| (tier_idx as u32, tier.weight))
+        .filter(|&(tier_idx, _)| {
+            list_weighted
+                .iter()
+                .any(|&(_, _, slot_tier)| slot_tier == Some(tier_idx))
+        })
+        .collect();
+
+    if tier_weighted.is_empty() {
+        let slot_weighted: Vec<(u32, u32)> = list_weighted
+            .iter()
+            .map(|&(idx, weight, _)| (idx, weight))
+            .collect();
+        let slot_idx =
+            select_from_weighted(randomness, &slot_weighted).map_err(StdError::generic_err)?;
+        return Ok((slot_idx, None));
+    }
+
+    let tier_idx =
+        select_from_weighted(randomness, &tier_weighted).map_err(StdError::generic_err)?;
+
+    let slot_weighted: Vec<(u32, u32)> = list_weighted
+        .iter()
+        .filter(|&&(_, _, slot_tier)| slot_tier == Some(tier_idx))
+        .map(|&(idx, weight, _)| (idx, weight))
+        .collect();
+
+    // use another randomness for selecting slot inside the tier
+    let slot_randomness = tier_slot_randomness(randomness);
+
+    let slot_idx =
+        select_from_weighted(slot_randomness, &slot_weighted).map_err(StdError::generic_err)?;
+    Ok((slot_idx, Some(tier_idx)))
+}
+
+/// Randomness for selecting slot inside the selected tier
+fn tier_slot_randomness(randomness: [u8; 32]) -> [u8; 32] {
+    sub_randomness_with_key(randomness, "tier").provide()
+}
+
+/// Count spins and their payment in wheel statistics
+fn add_spin_stats(
+    storage: &mut dyn Storage,
+    price: &Coin,
+    spins: u32,
+    paid_spins: u32,
+    is_new_player: bool,
+) -> Result<(), ContractError> {
+    let mut stats = WHEEL_STATS.may_load(storage)?.unwrap_or_default();
+
+    stats.total_spins += spins as u64;
+    if is_new_player {
+        stats.unique_players += 1;
+    }
+
+    let payment = checked_u128_mul_u32(price.amount, paid_spins);
+    if !payment.is_zero() {
+        if let Some(revenue) = stats.revenue.iter_mut().find(|c| c.denom == price.denom) {
+            revenue.amount = revenue.amount.checked_add(payment).unwrap();
+        } else {
+            stats
+                .revenue
+                .push(Coin::new(payment.u128(), price.denom.clone()));
+        }
+    }
+
+    WHEEL_STATS.save(storage, &stats)?;
+
+    Ok(())
+}
+
+/// Remove refunded spins and their payment from wheel statistics
+fn remove_spin_stats(
+    storage: &mut dyn Storage,
+    price: &Coin,
+    spins: u32,
+    paid_spins: u32,
+    is_last_spins: bool,
+) -> Result<(), ContractError> {
+    let mut stats = WHEEL_STATS.may_load(storage)?.unwrap_or_default();
+
+    stats.total_spins = stats.total_spins.saturating_sub(spins as u64);
+    if is_last_spins {
+        stats.unique_players = stats.unique_players.saturating_sub(1);
+    }
+
+    let payment = checked_u128_mul_u32(price.amount, paid_spins);
+    if let Some(revenue) = stats.revenue.iter_mut().find(|c| c.denom == price.denom) {
+        revenue.amount = revenue.amount.saturating_sub(payment);
+    }
+
+    WHEEL_STATS.save(storage, &stats)?;
+
+    Ok(())
+}
+
+fn add_won_stats(
+    storage: &mut dyn Storage,
+    slot_id: u32,
+    is_claimed: bool,
+) -> Result<(), ContractError> {
+    let mut stats = SLOT_STATS.may_load(storage, slot_id)?.unwrap_or_default();
+
+    stats.won += 1;
+    if is_claimed {
+        stats.claimed += 1;
+    }
+
+    SLOT_STATS.save(storage, slot_id, &stats)?;
 
-                spins_result.push((false, reward));
-            }
+    Ok(())
+}
 
-            WheelReward::FungibleToken(ref mut token) => {
-                // spin result with token as reward
-                let reward = WheelReward::FungibleToken(TokenReward {
-                    label: token.label.clone(),
-                    token_address: token.token_address.clone(),
-                    amount: token.amount,
-                    number: 1,
-                    id: token.id.clone(),
-                });
+fn add_claimed_stats(storage: &mut dyn Storage, slot_id: u32) -> Result<(), ContractError> {
+    let mut stats = SLOT_STATS.may_load(storage, slot_id)?.unwrap_or_default();
 
-                token.number -= 1;
+    stats.claimed += 1;
 
-                spins_result.push((false, reward));
-            }
+    SLOT_STATS.save(storage, slot_id, &stats)?;
 
-            WheelReward::Coin(ref mut coin) => {
-                // spin result with coin as reward
-                let reward = WheelReward::Coin(CoinReward {
-                    label: coin.label.clone(),
-                    coin: coin.coin.clone(),
-                    number: 1,
-                    id: coin.id.clone(),
-                });
+    Ok(())
+}
 
-                coin.number -= 1;
+/// Give the jackpot pool to the winner and reset it to the seed amount from the reserve
+fn win_jackpot(jackpot: &mut Jackpot) -> WheelReward {
+    let reward = WheelReward::Coin(CoinReward {
+        label: jackpot.label.clone(),
+        coin: Coin {
+            denom: jackpot.denom.clone(),
+            amount: jackpot.pool,
+        },
+        number: 1,
+        id: JACKPOT_REWARD_ID,
+        tier: None,
+        vesting: None,
+    });
 
-                spins_result.push((false, reward));
-            }
+    // the pool is already locked, it stays locked until the winner claims it
+    let seed_amount = jackpot.seed_amount.min(jackpot.reserve);
+    jackpot.reserve = jackpot.reserve.checked_sub(seed_amount).unwrap();
+    jackpot.pool = seed_amount;
 
-            WheelReward::Text(ref mut text) => {
-                // spin result with text as reward
-                let reward = WheelReward::Text(TextReward {
-                    label: text.label.clone(),
-                    number: 1,
-                    id: text.id.clone(),
-                });
+    reward
+}
 
-                text.number -= 1;
+/// Unlock the jackpot pool and reserve after the round, when no spin can win them anymore
+fn release_jackpot(storage: &mut dyn Storage, denom: &str) -> Result<(), ContractError> {
+    let mut jackpot = match JACKPOT.may_load(storage)? {
+        Some(jackpot) if jackpot.denom == denom => jackpot,
+        _ => return Ok(()),
+    };
 
-                spins_result.push((false, reward));
-            }
-        }
+    let released = jackpot
+        .pool
+        .checked_add(jackpot.reserve)
+        .map_err(StdError::from)?;
+    if !released.is_zero() {
+        unlock_coin(storage, jackpot.denom.clone(), released)?;
+        jackpot.pool = Uint128::zero();
+        jackpot.reserve = Uint128::zero();
+        JACKPOT.save(storage, &jackpot)?;
     }
 
-    // update spins result
-    SPINS_RESULT.save(storage, player, &spins_result)?;
+    Ok(())
+}
+
+/// Check if the address is whitelisted or has spin results, results are kept after whitelist removal
+fn is_player(storage: &dyn Storage, player: &Addr) -> bool {
+    WHITELIST.has(storage, player.clone())
+        || spin_records()
+            .prefix(player.clone())
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+}
 
-    // update wheel rewards
-    WHEEL_REWARDS.save(storage, &(supply, wheel_rewards))?;
+/// Make a unique number and id for a new random job of player
+fn next_job_id(storage: &mut dyn Storage, player: &Addr) -> StdResult<(u64, String)> {
+    let job_count = JOB_COUNT.may_load(storage)?.unwrap_or_default();
+    JOB_COUNT.save(storage, &(job_count + 1))?;
+
+    Ok((job_count, format!("{}/{}", player, job_count)))
+}
+
+/// Save a pending job and queue it by its number
+fn save_random_job(
+    storage: &mut dyn Storage,
+    job_id: &str,
+    random_job: &RandomJob,
+) -> StdResult<()> {
+    RANDOM_JOBS.save(storage, job_id.to_string(), random_job)?;
+    JOB_QUEUE.save(storage, random_job.number, &job_id.to_string())
+}
+
+/// Remove a finished or cancelled job from pending jobs and the queue
+fn remove_random_job(storage: &mut dyn Storage, job_id: &str, random_job: &RandomJob) {
+    RANDOM_JOBS.remove(storage, job_id.to_string());
+    JOB_QUEUE.remove(storage, random_job.number);
+}
 
-    Ok(randomness)
+/// Check if there are spins waiting for randomness or for reveal
+fn has_pending_spins(storage: &dyn Storage) -> bool {
+    RANDOM_JOBS
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+        || SPIN_COMMITS
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
+}
+
+/// Generate messages for delivering reward won by player
+fn claim_reward_msgs(
+    storage: &mut dyn Storage,
+    reward: WheelReward,
+    recipient: String,
+    msgs: &mut Vec<CosmosMsg>,
+) -> Result<(), ContractError> {
+    match reward {
+        // won nfts are only minted on claim
+        WheelReward::MintNft(nft) => mint_nft_msgs(msgs, recipient, nft),
+        // contracts are only called on claim
+        WheelReward::ContractCall(call) => contract_call_msgs(msgs, recipient, call),
+        _ => {
+            withdraw_reward_msgs(storage, reward, recipient, msgs)?;
+            Ok(())
+        }
+    }
 }
 
 fn withdraw_reward_msgs(
@@ -951,16 +3828,7 @@ fn withdraw_reward_msgs(
             let total_amount = checked_u128_mul_u32(coin.coin.amount, coin.number);
 
             // remove locked amount
-            let locked_amount = LOCKED_COINS.load(storage, coin.coin.denom.clone())?;
-            if locked_amount <= total_amount {
-                LOCKED_COINS.remove(storage, coin.coin.denom.clone());
-            } else {
-                LOCKED_COINS.save(
-                    storage,
-                    coin.coin.denom.clone(),
-                    &locked_amount.checked_sub(total_amount).unwrap(),
-                )?;
-            }
+            unlock_coin(storage, coin.coin.denom.clone(), total_amount)?;
 
             let total_coin = Coin {
                 denom: coin.coin.denom,
@@ -975,11 +3843,124 @@ fn withdraw_reward_msgs(
             coin.number
         }
         WheelReward::Text(text) => text.number,
+        WheelReward::RandomCoin(coin) => {
+            // won amount of a won reward, the whole pool of a slot
+            let total_amount = coin.amount.unwrap_or(coin.pool);
+
+            // remove locked amount
+            unlock_coin(storage, coin.denom.clone(), total_amount)?;
+
+            if total_amount > Uint128::zero() {
+                // send coin to recipient
+                send_coin_msg(
+                    msgs,
+                    recipient,
+                    vec![Coin::new(total_amount.u128(), coin.denom)],
+                )?;
+            }
+
+            coin.number
+        }
+        WheelReward::RandomToken(token) => {
+            // won amount of a won reward, the whole pool of a slot
+            let total_amount = token.amount.unwrap_or(token.pool);
+
+            if total_amount > Uint128::zero() {
+                // create msg for transfering token to recipient
+                transfer_token_msg(msgs, recipient, token.token_address, total_amount)?;
+            }
+
+            token.number
+        }
+        // nfts are not minted yet, just remove the remaining supply
+        WheelReward::MintNft(nft) => nft.number,
+        // contracts are not called, just remove the remaining supply
+        WheelReward::ContractCall(call) => call.number,
+        // codes are delivered off-chain
+        WheelReward::Voucher(voucher) => voucher.code_hashes.len() as u32,
+        WheelReward::Bundle(bundle) => {
+            let supply = bundle.bundles.len() as u32;
+
+            for item in bundle.bundles {
+                bundle_msgs(storage, item, recipient.clone(), msgs)?;
+            }
+
+            supply
+        }
     };
 
     Ok(removed_supply)
 }
 
+/// Generate messages for delivering all assets in bundle
+fn bundle_msgs(
+    storage: &mut dyn Storage,
+    bundle: Bundle,
+    recipient: String,
+    msgs: &mut Vec<CosmosMsg>,
+) -> Result<(), ContractError> {
+    for nft in bundle.nfts {
+        transfer_nft_msgs(
+            msgs,
+            recipient.clone(),
+            nft.collection_address,
+            vec![nft.token_id],
+        )?;
+    }
+
+    for token in bundle.tokens {
+        if token.amount > Uint128::zero() {
+            transfer_token_msg(msgs, recipient.clone(), token.token_address, token.amount)?;
+        }
+    }
+
+    let mut coins: Vec<Coin> = Vec::with_capacity(bundle.coins.len());
+    for coin in bundle.coins {
+        unlock_coin(storage, coin.denom.clone(), coin.amount)?;
+
+        if coin.amount > Uint128::zero() {
+            coins.push(coin);
+        }
+    }
+
+    if !coins.is_empty() {
+        send_coin_msg(msgs, recipient, coins)?;
+    }
+
+    Ok(())
+}
+
+/// Lock coins so that they can not be withdrawn by `withdraw_coin`
+fn lock_coin(
+    storage: &mut dyn Storage,
+    denom: String,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let locked_amount = LOCKED_COINS
+        .may_load(storage, denom.clone())?
+        .unwrap_or_default();
+
+    LOCKED_COINS.save(storage, denom, &locked_amount.checked_add(amount).unwrap())?;
+
+    Ok(())
+}
+
+/// Unlock coins when they are sent out of contract
+fn unlock_coin(
+    storage: &mut dyn Storage,
+    denom: String,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let locked_amount = LOCKED_COINS.load(storage, denom.clone())?;
+    if locked_amount <= amount {
+        LOCKED_COINS.remove(storage, denom);
+    } else {
+        LOCKED_COINS.save(storage, denom, &locked_amount.checked_sub(amount).unwrap())?;
+    }
+
+    Ok(())
+}
+
 /// Generate messages for transfering nfts
 fn transfer_nft_msgs(
     msgs: &mut Vec<CosmosMsg>,
@@ -1002,6 +3983,53 @@ fn transfer_nft_msgs(
     Ok(())
 }
 
+/// Generate messages for minting nfts
+fn mint_nft_msgs(
+    msgs: &mut Vec<CosmosMsg>,
+    recipient: String,
+    nft: MintNftReward,
+) -> Result<(), ContractError> {
+    for serial in nft.next_serial..(nft.next_serial + nft.number) {
+        let token_id = format!("{}{}", nft.token_id_prefix, serial);
+        let token_uri = nft
+            .token_uri
+            .as_ref()
+            .map(|uri| uri.replace("{token_id}", &token_id));
+
+        let mint_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: nft.collection_address.clone(), // nft contract
+            msg: to_json_binary(&Cw721BaseExecuteMsg::<Cw721BaseExtension, Empty>::Mint {
+                token_id,
+                owner: recipient.clone(),
+                token_uri,
+                extension: None,
+            })?,
+            funds: vec![],
+        });
+
+        msgs.push(mint_msg);
+    }
+    Ok(())
+}
+
+/// generate messages for calling target contract on behalf of winner
+fn contract_call_msgs(
+    msgs: &mut Vec<CosmosMsg>,
+    recipient: String,
+    call: ContractCallReward,
+) -> Result<(), ContractError> {
+    for _ in 0..call.number {
+        let call_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: call.contract_address.clone(), // target contract
+            msg: Binary::from(call.msg.replace("{winner}", &recipient).as_bytes()),
+            funds: vec![],
+        });
+
+        msgs.push(call_msg);
+    }
+    Ok(())
+}
+
 /// generate message for transfering fungible token
 fn transfer_token_msg(
     msgs: &mut Vec<CosmosMsg>,
@@ -1074,6 +4102,66 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetWheelConfig {} => to_json_binary(&get_wheel_config(deps)?),
         QueryMsg::Spinnable { address } => to_json_binary(&spinnable(deps, env, address)?),
         QueryMsg::GetWhiteList {} => to_json_binary(&get_white_list(deps)?),
+        QueryMsg::GetPlayerLosingStreak { address } => {
+            to_json_binary(&get_player_losing_streak(deps, address)?)
+        }
+        QueryMsg::GetJackpot {} => to_json_binary(&get_jackpot(deps)?),
+        QueryMsg::GetTiers {} => to_json_binary(&get_tiers(deps)?),
+        QueryMsg::GetSpinCommit { address } => to_json_binary(&get_spin_commit(deps, address)?),
+        QueryMsg::GetPendingJobs { address } => to_json_binary(&get_pending_jobs(deps, address)?),
+        QueryMsg::GetSpinProof { address, index } => {
+            to_json_binary(&get_spin_proof(deps, address, index)?)
+        }
+        QueryMsg::VerifySpin {
+            address,
+            index,
+            weights,
+        } => to_json_binary(&verify_spin(deps, address, index, weights)?),
+        QueryMsg::ListPendingJobs { start_after, limit } => {
+            to_json_binary(&list_pending_jobs(deps, start_after, limit)?)
+        }
+        QueryMsg::GetPlayerResults {
+            address,
+            start_after,
+            limit,
+        } => to_json_binary(&get_player_results(deps, address, start_after, limit)?),
+        QueryMsg::ListSlotResults {
+            slot_id,
+            start_after,
+            limit,
+        } => to_json_binary(&list_slot_results(deps, slot_id, start_after, limit)?),
+        QueryMsg::GetWheelStats {} => to_json_binary(&get_wheel_stats(deps)?),
+        QueryMsg::GetLeaderboardConfig {} => to_json_binary(&get_leaderboard_config(deps)?),
+        QueryMsg::GetLeaderboard {
+            order_by,
+            start_after,
+            limit,
+        } => to_json_binary(&get_leaderboard(deps, order_by, start_after, limit)?),
+        QueryMsg::GetReferralReward {} => to_json_binary(&get_referral_reward(deps)?),
+        QueryMsg::GetReferral { address } => to_json_binary(&get_referral(deps, address)?),
+        QueryMsg::GetReferralRewards { address } => {
+            to_json_binary(&get_referral_rewards(deps, address)?)
+        }
+        QueryMsg::ListWinners {
+            start_before,
+            limit,
+            exclude_losing,
+        } => to_json_binary(&list_winners(deps, start_before, limit, exclude_losing)?),
+        QueryMsg::ListResultsByTime {
+            start,
+            end,
+            start_after,
+            limit,
+        } => to_json_binary(&list_results_by_time(deps, start, end, start_after, limit)?),
+        QueryMsg::GetSeedCommitment {} => to_json_binary(&get_seed_commitment(deps)?),
+        QueryMsg::GetPlayerVestings { address } => {
+            to_json_binary(&get_player_vestings(deps, env, address)?)
+        }
+        QueryMsg::GetCallTargets {} => to_json_binary(&get_call_targets(deps)?),
+        QueryMsg::GetOperators {} => to_json_binary(&get_operators(deps)?),
+        QueryMsg::GetVoucherRedemption { slot_id, index } => {
+            to_json_binary(&get_voucher_redemption(deps, slot_id, index)?)
+        }
     }
 }
 
@@ -1082,13 +4170,438 @@ fn get_wheel_rewards(deps: Deps) -> StdResult<(u32, Vec<WheelReward>)> {
 }
 
 fn get_player_rewards(deps: Deps, address: String) -> StdResult<Option<Vec<(bool, WheelReward)>>> {
-    SPINS_RESULT.may_load(deps.storage, Addr::unchecked(address))
+    let player = Addr::unchecked(address);
+    if !is_player(deps.storage, &player) {
+        return Ok(None);
+    }
+
+    let rewards = spin_records()
+        .prefix(player)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, record)| (record.is_claimed, record.reward)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Some(rewards))
 }
 
 fn get_player_spinned(deps: Deps, address: String) -> StdResult<Option<u32>> {
     WHITELIST.may_load(deps.storage, Addr::unchecked(address))
 }
 
+fn get_player_losing_streak(deps: Deps, address: String) -> StdResult<u32> {
+    Ok(LOSING_STREAKS
+        .may_load(deps.storage, Addr::unchecked(address))?
+        .unwrap_or(0))
+}
+
+fn get_jackpot(deps: Deps) -> StdResult<Option<Jackpot>> {
+    JACKPOT.may_load(deps.storage)
+}
+
+fn get_spin_commit(deps: Deps, address: String) -> StdResult<Option<SpinCommit>> {
+    SPIN_COMMITS.may_load(deps.storage, Addr::unchecked(address))
+}
+
+fn get_player_results(
+    deps: Deps,
+    address: String,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<Vec<SpinRecord>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    spin_records()
+        .prefix(Addr::unchecked(address))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, record)| record))
+        .collect()
+}
+
+fn list_slot_results(
+    deps: Deps,
+    slot_id: u32,
+    start_after: Option<(String, u32)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<SpinRecord>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start =
+        start_after.map(|(player, index)| Bound::exclusive((Addr::unchecked(player), index)));
+
+    spin_records()
+        .idx
+        .slot
+        .prefix(slot_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, record)| record))
+        .collect()
+}
+
+fn get_wheel_stats(deps: Deps) -> StdResult<WheelStatsResponse> {
+    let stats = WHEEL_STATS.may_load(deps.storage)?.unwrap_or_default();
+    let (supply, wheel_rewards) = WHEEL_REWARDS.load(deps.storage)?;
+
+    let slot_stats = SLOT_STATS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let slot_response = |slot_id: u32, remaining: u32| {
+        let stats = slot_stats
+            .iter()
+            .find(|(id, _)| *id == slot_id)
+            .map(|(_, stats)| stats.clone())
+            .unwrap_or_default();
+
+        SlotStatsResponse {
+            slot_id,
+            won: stats.won,
+            claimed: stats.claimed,
+            unclaimed: stats.won.saturating_sub(stats.claimed),
+            remaining,
+        }
+    };
+
+    // slots of the wheel, then won slots which are no longer in the wheel like the jackpot
+    let mut slots: Vec<SlotStatsResponse> = wheel_rewards
+        .iter()
+        .map(|reward| slot_response(reward.get_id(), reward.get_supply()))
+        .collect();
+    for (slot_id, _) in slot_stats.iter() {
+        if !slots.iter().any(|slot| slot.slot_id == *slot_id) {
+            slots.push(slot_response(*slot_id, 0));
+        }
+    }
+
+    Ok(WheelStatsResponse {
+        total_spins: stats.total_spins,
+        unique_players: stats.unique_players,
+        revenue: stats.revenue,
+        slots,
+        remaining_supply: supply,
+    })
+}
+
+fn get_leaderboard_config(deps: Deps) -> StdResult<Option<Leaderboard>> {
+    LEADERBOARD.may_load(deps.storage)
+}
+
+fn get_referral_reward(deps: Deps) -> StdResult<Option<ReferralReward>> {
+    REFERRAL_REWARD.may_load(deps.storage)
+}
+
+fn get_referral(deps: Deps, address: String) -> StdResult<Option<Referral>> {
+    let address = deps.api.addr_validate(&address)?;
+    REFERRALS.may_load(deps.storage, address)
+}
+
+fn get_referral_rewards(deps: Deps, address: String) -> StdResult<ReferralRewardsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+
+    Ok(ReferralRewardsResponse {
+        earnings: REFERRAL_EARNINGS
+            .may_load(deps.storage, address.clone())?
+            .unwrap_or_default(),
+        free_spins: FREE_SPINS.may_load(deps.storage, address)?.unwrap_or(0),
+    })
+}
+
+fn get_leaderboard(
+    deps: Deps,
+    order_by: LeaderboardOrder,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PlayerScore>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // players are listed from the highest score
+    let start = match start_after {
+        Some(address) => Some(
+            player_scores()
+                .may_load(deps.storage, Addr::unchecked(address))?
+                .ok_or_else(|| StdError::not_found("player score"))?,
+        ),
+        None => None,
+    };
+
+    let scores = match order_by {
+        LeaderboardOrder::Value => player_scores().idx.value.range(
+            deps.storage,
+            None,
+            start.map(|score| Bound::exclusive((score.value.u128(), score.player))),
+            Order::Descending,
+        ),
+        LeaderboardOrder::Spins => player_scores().idx.spins.range(
+            deps.storage,
+            None,
+            start.map(|score| Bound::exclusive((score.spins, score.player))),
+            Order::Descending,
+        ),
+    };
+
+    scores
+        .take(limit)
+        .map(|item| item.map(|(_, score)| score))
+        .collect()
+}
+
+fn list_winners(
+    deps: Deps,
+    start_before: Option<u64>,
+    limit: Option<u32>,
+    exclude_losing: Option<bool>,
+) -> StdResult<Vec<WinnerResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let end = start_before.map(Bound::exclusive);
+    let exclude_losing = exclude_losing.unwrap_or(true);
+
+    spin_records()
+        .idx
+        .spin_id
+        .range(deps.storage, None, end, Order::Descending)
+        .filter(|item| match item {
+            Ok((_, record)) => !(exclude_losing && record.reward.is_losing()),
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|item| {
+            item.map(|(_, record)| WinnerResponse {
+                spin_id: record.spin_id,
+                player: record.player,
+                slot_id: record.reward.get_id(),
+                label: record.reward.get_label(),
+                time: record.time,
+            })
+        })
+        .collect()
+}
+
+fn list_results_by_time(
+    deps: Deps,
+    start: Option<Timestamp>,
+    end: Option<Timestamp>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<SpinRecord>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // spin ids increase with time, so records are listed by spin id from the first one in range
+    let first_spin_id = match start {
+        Some(start) => {
+            let first = spin_records()
+                .idx
+                .time
+                .prefix_range_raw(
+                    deps.storage,
+                    Some(PrefixBound::inclusive(start.nanos())),
+                    None,
+                    Order::Ascending,
+                )
+                .next()
+                .transpose()?;
+
+            match first {
+                Some((_, record)) => record.spin_id,
+                None => return Ok(Vec::new()),
+            }
+        }
+        None => 0,
+    };
+    let min = match start_after {
+        Some(spin_id) if spin_id >= first_spin_id => Bound::exclusive(spin_id),
+        _ => Bound::inclusive(first_spin_id),
+    };
+
+    spin_records()
+        .idx
+        .spin_id
+        .range(deps.storage, Some(min), None, Order::Ascending)
+        .map(|item| item.map(|(_, record)| record))
+        .take_while(|item| match (item, end) {
+            (Ok(record), Some(end)) => record.time < end,
+            _ => true,
+        })
+        .take(limit)
+        .collect()
+}
+
+fn get_spin_proof(deps: Deps, address: String, index: u32) -> StdResult<Option<SpinProof>> {
+    SPIN_PROOFS.may_load(deps.storage, (Addr::unchecked(address), index))
+}
+
+/// Hash of a weighted list of slots, only the hash is saved in spin proofs
+fn weights_hash(weights: &[(u32, u32, Option<u32>)]) -> StdResult<HexBinary> {
+    Ok(HexBinary::from(
+        Sha256::digest(to_json_vec(weights)?).as_slice(),
+    ))
+}
+
+fn verify_spin(
+    deps: Deps,
+    address: String,
+    index: u32,
+    weights: Vec<(u32, u32, Option<u32>)>,
+) -> StdResult<VerifySpinResponse> {
+    let proof = SPIN_PROOFS.load(deps.storage, (Addr::unchecked(address), index))?;
+
+    // randomness must be derived from the recorded seed and key
+    let seed: [u8; 32] = proof
+        .seed
+        .to_array()
+        .map_err(|_| StdError::generic_err("invalid seed"))?;
+    let mut provider = sub_randomness_with_key(seed, proof.key);
+    let mut randomness = provider.provide();
+    for _ in 0..proof.nonce {
+        randomness = provider.provide();
+    }
+
+    // the given weights must be the ones the spin is selected from
+    if proof.randomness.as_slice() != randomness || weights_hash(&weights)? != proof.weights_hash {
+        return Ok(VerifySpinResponse {
+            is_valid: false,
+            selected_slot: None,
+        });
+    }
+
+    // select the tier and the slot again from the given weights and the wheel tiers
+    let tiers = TIERS.may_load(deps.storage)?.unwrap_or_default();
+    let (slot_idx, tier_idx) = select_slot(randomness, &weights, &tiers)?;
+
+    Ok(VerifySpinResponse {
+        is_valid: slot_idx == proof.slot_index && tier_idx == proof.tier_index,
+        selected_slot: Some(slot_idx),
+    })
+}
+
+fn get_pending_jobs(deps: Deps, address: String) -> StdResult<Vec<PendingJobResponse>> {
+    // job ids of player are prefixed with `{address}/`
+    let min = Bound::inclusive(format!("{}/", address));
+    let max = Bound::exclusive(format!("{}0", address));
+
+    RANDOM_JOBS
+        .range(deps.storage, Some(min), Some(max), Order::Ascending)
+        .map(|item| item.map(pending_job_response))
+        .collect()
+}
+
+fn list_pending_jobs(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PendingJobResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    RANDOM_JOBS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(pending_job_response))
+        .collect()
+}
+
+fn pending_job_response((job_id, job): (String, RandomJob)) -> PendingJobResponse {
+    PendingJobResponse {
+        job_id,
+        player: job.player,
+        spins: job.spins,
+        submitted_at: job.submitted_at,
+    }
+}
+
+fn get_seed_commitment(deps: Deps) -> StdResult<Option<SeedCommitment>> {
+    SEED_COMMITMENT.may_load(deps.storage)
+}
+
+fn get_player_vestings(deps: Deps, env: Env, address: String) -> StdResult<Vec<VestingResponse>> {
+    let vestings = VESTINGS
+        .may_load(deps.storage, Addr::unchecked(address))?
+        .unwrap_or_default();
+
+    Ok(vestings
+        .into_iter()
+        .map(|vesting| {
+            let vested = vesting.vested(env.block.time);
+
+            VestingResponse {
+                label: vesting.label,
+                asset: vesting.asset,
+                vested,
+                released: vesting.released,
+                claimable: vested.checked_sub(vesting.released).unwrap(),
+                cliff_end: vesting.start.plus_seconds(vesting.cliff),
+                end: vesting.start.plus_seconds(vesting.duration),
+            }
+        })
+        .collect())
+}
+
+fn get_call_targets(deps: Deps) -> StdResult<Vec<Addr>> {
+    CALL_TARGETS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+fn get_operators(deps: Deps) -> StdResult<Vec<Addr>> {
+    OPERATORS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+fn get_voucher_redemption(
+    deps: Deps,
+    slot_id: u32,
+    index: u32,
+) -> StdResult<Option<VoucherRedemption>> {
+    VOUCHER_REDEMPTIONS.may_load(deps.storage, (slot_id, index))
+}
+
+fn get_tiers(deps: Deps) -> StdResult<Vec<TierResponse>> {
+    let tiers = TIERS.may_load(deps.storage)?.unwrap_or_default();
+    let (_, wheel_rewards) = WHEEL_REWARDS.load(deps.storage)?;
+
+    // remaining supply of each tier
+    let supplies: Vec<u32> = tiers
+        .iter()
+        .map(|tier| {
+            wheel_rewards
+                .iter()
+                .filter(|reward| reward.get_tier().as_ref() == Some(&tier.name))
+                .map(|reward| reward.get_supply())
+                .sum()
+        })
+        .collect();
+
+    // odds are shared among the tiers that still have rewards
+    let total_weight: u32 = tiers
+        .iter()
+        .zip(supplies.iter())
+        .filter(|(_, &supply)| supply > 0)
+        .map(|(tier, _)| tier.weight)
+        .sum();
+
+    let resp = tiers
+        .into_iter()
+        .zip(supplies)
+        .map(|(tier, supply)| {
+            let odds = if supply > 0 {
+                Decimal::from_ratio(tier.weight, total_weight)
+            } else {
+                Decimal::zero()
+            };
+
+            TierResponse {
+                name: tier.name,
+                weight: tier.weight,
+                supply,
+                odds,
+            }
+        })
+        .collect();
+
+    Ok(resp)
+}
+
 fn get_wheel_config(deps: Deps) -> StdResult<Config> {
     CONFIG.load(deps.storage)
 }
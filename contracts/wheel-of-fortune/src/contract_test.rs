@@ -1,25 +1,39 @@
 #[cfg(test)]
+#[allow(clippy::vec_init_then_push)]
 mod unit_tests {
     use std::str::FromStr;
 
-    use crate::contract::{execute, instantiate};
+    use crate::contract::{execute, instantiate, query, reply};
 
     use crate::error::ContractError;
-    use crate::msg::{ExecuteMsg, InstantiateMsg};
+    use crate::msg::{
+        ConfigUpdate, ExecuteMsg, FieldUpdate, InstantiateMsg, LeaderboardOrder, MockExecuteMsg,
+        OperatorExecuteMsg, PendingJobResponse, QueryMsg, RandomnessProviderMsg,
+        ReferralRewardsResponse, SlotStatsResponse, SpinResponse, TierResponse, VerifySpinResponse,
+        VestingResponse, WheelStatsResponse, WinnerResponse,
+    };
     use crate::state::{
-        AdminConfig, CoinReward, CollectionReward, TextReward, TokenReward, WheelReward,
-        ADMIN_CONFIG, WHEEL_REWARDS,
+        player_scores, spin_records, AdminConfig, Bundle, BundleReward, CoinReward,
+        CollectionReward, ContractCallReward, MintNftReward, NftAsset, PlayerScore,
+        RandomCoinReward, RandomnessMode, RandomnessProvider, Referral, ReferralReward, SpinCommit,
+        SpinProof, SpinRecord, TextReward, Tier, TokenAsset, TokenReward, VestingAsset,
+        VestingSchedule, VoucherRedemption, VoucherReward, WheelReward, ADMIN_CONFIG, CONFIG,
+        FREE_SPINS, JACKPOT, LOCKED_COINS, SPIN_COMMITS, SPIN_PROOFS, TIERS, VESTINGS,
+        WHEEL_REWARDS, WHITELIST,
     };
 
     use cosmwasm_std::testing::{
         mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
     };
     use cosmwasm_std::{
-        coins, to_json_binary, Addr, BlockInfo, Coin, ContractInfo, CosmosMsg, Env, OwnedDeps,
-        Response, Timestamp, Uint128, WasmMsg,
+        coins, from_json, to_json_binary, Addr, BankMsg, Binary, BlockInfo, Coin, ContractInfo,
+        ContractResult, CosmosMsg, Decimal, Empty, Env, Event, HexBinary, OwnedDeps, Reply,
+        ReplyOn, Response, StdError, SubMsgResult, SystemResult, Timestamp, Uint128, WasmMsg,
     };
     use cw20::Cw20ExecuteMsg;
-    use cw721_base::{ExecuteMsg as CW721ExecuteMsg, Extension as CW721Extension};
+    use cw721_base::{ExecuteMsg as CW721ExecuteMsg, Extension as CW721Extension, MinterResponse};
+    use nois::NoisCallback;
+    use sha2::{Digest, Sha256};
 
     const CREATOR: &str = "creator";
     const USER: &str = "user";
@@ -36,14 +50,106 @@ mod unit_tests {
             max_spins_per_address: 100,
             is_public: true,
             is_advanced_randomness: false,
-            nois_proxy: NOIS_PROXY.to_string(),
+            nois_proxy: Some(NOIS_PROXY.to_string()),
+            pity_threshold: None,
+            is_commit_reveal: false,
+            spin_reveal_window: None,
+            reveal_timeout: None,
+            randomness_provider: RandomnessProviderMsg::Nois {},
         };
 
         let info = mock_info(CREATOR, &[]);
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
 
-        return deps;
+        deps
+    }
+
+    fn player_rewards(
+        deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        player: &str,
+    ) -> Vec<(bool, WheelReward)> {
+        let rewards: Option<Vec<(bool, WheelReward)>> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetPlayerRewards {
+                    address: player.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        rewards.unwrap()
+    }
+
+    // player won the reward as their next result
+    fn save_won_reward(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        player: &str,
+        reward: WheelReward,
+    ) {
+        let player = Addr::unchecked(player);
+        if !WHITELIST.has(deps.as_ref().storage, player.clone()) {
+            WHITELIST
+                .save(deps.as_mut().storage, player.clone(), &0)
+                .unwrap();
+        }
+
+        let index = player_rewards(deps, player.as_str()).len() as u32;
+        spin_records()
+            .save(
+                deps.as_mut().storage,
+                (player.clone(), index),
+                &SpinRecord {
+                    spin_id: index as u64 + 1,
+                    player,
+                    index,
+                    reward,
+                    is_claimed: false,
+                    height: 1,
+                    time: Timestamp::from_seconds(1),
+                    price: Coin::new(1000, "uaura"),
+                    randomness_mode: RandomnessMode::Basic,
+                    job_id: None,
+                    claimed_at: None,
+                    claim_recipient: None,
+                },
+            )
+            .unwrap();
+    }
+
+    fn text_spin_data(job_id: Option<&str>, indexes: &[u32]) -> Binary {
+        to_json_binary(&SpinResponse {
+            player: Addr::unchecked(USER),
+            job_id: job_id.map(|job_id| job_id.to_string()),
+            rewards: indexes
+                .iter()
+                .map(|&index| {
+                    (
+                        index,
+                        WheelReward::Text(TextReward {
+                            label: "text".to_string(),
+                            number: 1,
+                            id: 1,
+                            tier: None,
+                            is_losing: false,
+                        }),
+                    )
+                })
+                .collect(),
+        })
+        .unwrap()
+    }
+
+    fn text_won_event(spin_id: u64, index: u32) -> Event {
+        Event::new("reward_won")
+            .add_attribute("player", USER)
+            .add_attribute("spin_id", spin_id.to_string())
+            .add_attribute("index", index.to_string())
+            .add_attribute("slot_id", "1")
+            .add_attribute("label", "text")
+            .add_attribute("kind", "text")
     }
 
     fn env_with_specify(block_time: Timestamp, block_height: u64) -> Env {
@@ -78,7 +184,12 @@ mod unit_tests {
             max_spins_per_address: 100, 
             is_public: true, 
             is_advanced_randomness: false, 
-            nois_proxy: NOIS_PROXY.to_string()
+            nois_proxy: Some(NOIS_PROXY.to_string()),
+            pity_threshold: None,
+            is_commit_reveal: false,
+            spin_reveal_window: None,
+            reveal_timeout: None,
+            randomness_provider: RandomnessProviderMsg::Nois {},
         };
 
         let info = mock_info(CREATOR, &[]);
@@ -99,7 +210,12 @@ mod unit_tests {
             max_spins_per_address: 0, // invalid maximum spins
             is_public: true,
             is_advanced_randomness: false,
-            nois_proxy: NOIS_PROXY.to_string(),
+            nois_proxy: Some(NOIS_PROXY.to_string()),
+            pity_threshold: None,
+            is_commit_reveal: false,
+            spin_reveal_window: None,
+            reveal_timeout: None,
+            randomness_provider: RandomnessProviderMsg::Nois {},
         };
 
         let info = mock_info(CREATOR, &[]);
@@ -115,6 +231,34 @@ mod unit_tests {
         };
     }
 
+    #[test]
+    fn instantiate_fail_with_invalid_pity_threshold() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            wheel_name: "test".to_string(),
+            random_seed: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+                .to_string(),
+            max_spins_per_address: 1,
+            is_public: true,
+            is_advanced_randomness: false,
+            nois_proxy: Some(NOIS_PROXY.to_string()),
+            pity_threshold: Some(0), // invalid pity threshold
+            is_commit_reveal: false,
+            spin_reveal_window: None,
+            reveal_timeout: None,
+            randomness_provider: RandomnessProviderMsg::Nois {},
+        };
+
+        let info = mock_info(CREATOR, &[]);
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        match res {
+            ContractError::CustomError { val } => {
+                assert_eq!(val, "the pity threshold must be greater than 0".to_string())
+            }
+            _ => panic!(),
+        };
+    }
+
     #[test]
     fn instantiate_fail_with_invalid_proxy_address() {
         let mut deps = mock_dependencies();
@@ -125,7 +269,12 @@ mod unit_tests {
             max_spins_per_address: 1,
             is_public: true,
             is_advanced_randomness: false,
-            nois_proxy: "".to_string(), // Invalid bench32 string address
+            nois_proxy: Some("".to_string()), // Invalid bench32 string address
+            pity_threshold: None,
+            is_commit_reveal: false,
+            spin_reveal_window: None,
+            reveal_timeout: None,
+            randomness_provider: RandomnessProviderMsg::Nois {},
         };
 
         let info = mock_info(CREATOR, &[]);
@@ -136,6 +285,47 @@ mod unit_tests {
         };
     }
 
+    #[test]
+    fn instantiate_fail_without_nois_proxy() {
+        let mut deps = mock_dependencies();
+        let mut msg = InstantiateMsg {
+            wheel_name: "test".to_string(),
+            random_seed: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+                .to_string(),
+            max_spins_per_address: 1,
+            is_public: true,
+            is_advanced_randomness: true,
+            nois_proxy: None,
+            pity_threshold: None,
+            is_commit_reveal: false,
+            spin_reveal_window: None,
+            reveal_timeout: None,
+            randomness_provider: RandomnessProviderMsg::Nois {},
+        };
+
+        let info = mock_info(CREATOR, &[]);
+        let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            _ => panic!(),
+        };
+
+        // other providers do not need the nois proxy
+        msg.randomness_provider = RandomnessProviderMsg::Vrf {
+            oracle: "oracle".to_string(),
+        };
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.nois_proxy, None);
+        assert_eq!(
+            config.randomness_provider,
+            RandomnessProvider::Vrf {
+                oracle: Addr::unchecked("oracle")
+            }
+        );
+    }
+
     #[test]
     #[should_panic]
     fn instantiate_fail_with_invalid_random_seed() {
@@ -147,7 +337,12 @@ mod unit_tests {
             max_spins_per_address: 1,
             is_public: true,
             is_advanced_randomness: false,
-            nois_proxy: NOIS_PROXY.to_string(),
+            nois_proxy: Some(NOIS_PROXY.to_string()),
+            pity_threshold: None,
+            is_commit_reveal: false,
+            spin_reveal_window: None,
+            reveal_timeout: None,
+            randomness_provider: RandomnessProviderMsg::Nois {},
         };
 
         let info = mock_info(CREATOR, &[]);
@@ -298,6 +493,55 @@ mod unit_tests {
         );
     }
 
+    #[test]
+    fn claim_reward_success_after_remove_whitelist() {
+        let mut deps = default_setup();
+
+        save_won_reward(
+            &mut deps,
+            USER,
+            WheelReward::Text(TextReward {
+                label: "text".to_string(),
+                number: 1,
+                id: 1,
+                tier: None,
+                is_losing: false,
+            }),
+        );
+
+        ADMIN_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &AdminConfig {
+                    admin: Addr::unchecked(CREATOR),
+                    activate: true,
+                },
+            )
+            .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::RemoveWhitelist {
+                addresses: vec![USER.to_string()],
+            },
+        )
+        .unwrap();
+
+        // results won before the removal can still be queried and claimed
+        assert_eq!(player_rewards(&deps, USER).len(), 1);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            ExecuteMsg::ClaimReward { rewards: vec![0] },
+        )
+        .unwrap();
+        assert!(player_rewards(&deps, USER)[0].0);
+    }
+
     /* ============================================================ AddReward ============================================================ */
     // Text
     #[test]
@@ -309,6 +553,8 @@ mod unit_tests {
                 label: "you lose".to_string(),
                 number: 100,
                 id: 1,
+                tier: None,
+                is_losing: false,
             }),
         };
 
@@ -334,6 +580,8 @@ mod unit_tests {
                 aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
                 number: 100,
                 id: 1,
+                tier: None,
+                is_losing: false,
             }) 
         };
 
@@ -364,6 +612,8 @@ mod unit_tests {
                 },
                 number: 100,
                 id: 1,
+                tier: None,
+                vesting: None,
             }),
         };
 
@@ -393,6 +643,8 @@ mod unit_tests {
                 coin: Coin { denom: "uaura".to_string(), amount: Uint128::from_str("100").unwrap() },
                 number: 100,
                 id: 1,
+                tier: None,
+                vesting: None,
             })
         };
 
@@ -426,6 +678,8 @@ mod unit_tests {
                 },
                 number: 100,
                 id: 1,
+                tier: None,
+                vesting: None,
             }),
         };
 
@@ -445,6 +699,39 @@ mod unit_tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn add_coin_reward_fail_with_invalid_vesting_schedule() {
+        let mut deps = default_setup();
+
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::Coin(CoinReward {
+                label: "100uaura".to_string(),
+                coin: Coin::new(100, "uaura"),
+                number: 100,
+                id: 1,
+                tier: None,
+                // cliff is longer than vesting duration
+                vesting: Some(VestingSchedule {
+                    cliff: 2000,
+                    duration: 1000,
+                }),
+            }),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &coins(10000u128, "uaura".to_string())),
+            add_reward,
+        )
+        .unwrap_err();
+
+        match res {
+            ContractError::InvalidVestingSchedule {} => {}
+            _ => panic!(),
+        }
+    }
     // Fungible Token
     #[test]
     fn add_token_reward_success() {
@@ -461,6 +748,8 @@ mod unit_tests {
                 amount,
                 number,
                 id: 1,
+                tier: None,
+                vesting: None,
             }),
         };
 
@@ -509,6 +798,8 @@ mod unit_tests {
                 amount,
                 number,
                 id: 1,
+                tier: None,
+                vesting: None,
             })
         };
 
@@ -539,6 +830,7 @@ mod unit_tests {
                 collection_address: test_address.to_string(),
                 token_ids: vec![nft_id.to_string()],
                 id: 1,
+                tier: None,
             }),
         };
 
@@ -586,6 +878,7 @@ mod unit_tests {
                 collection_address: test_address.to_string(),
                 token_ids: vec![nft_id.to_string()],
                 id: 1,
+                tier: None,
             })
         };
 
@@ -617,8 +910,9 @@ mod unit_tests {
             reward: WheelReward::NftCollection(CollectionReward {
                 label: "BBB collection".to_string(),
                 collection_address: test_address.to_string(),
-                token_ids: Vec::from(token_ids),
+                token_ids,
                 id: 1,
+                tier: None,
             }),
         };
 
@@ -635,90 +929,129 @@ mod unit_tests {
         }
     }
 
-    // general fail
+    // Random coin
     #[test]
-    fn add_reward_fail_with_unauthorized() {
+    fn add_random_coin_reward_success() {
         let mut deps = default_setup();
 
         let add_reward = ExecuteMsg::AddReward {
-            reward: WheelReward::Text(TextReward {
-                label: "you lose".to_string(),
-                number: 100,
+            reward: WheelReward::RandomCoin(RandomCoinReward {
+                label: "lucky aura".to_string(),
+                denom: "uaura".to_string(),
+                min: Uint128::from(10u128),
+                max: Uint128::from(1000u128),
+                pool: Uint128::from(5000u128),
+                amount: None,
+                number: 10,
                 id: 1,
+                tier: None,
             }),
         };
 
         let res = execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(USER /*sender is user*/, &[]),
+            mock_info(CREATOR, &coins(5000u128, "uaura".to_string())),
+            add_reward,
+        )
+        .unwrap();
+        assert_eq!(res, Response::new().add_attribute("action", "add_rewards"));
+
+        assert_eq!(
+            LOCKED_COINS
+                .load(deps.as_ref().storage, "uaura".to_string())
+                .unwrap(),
+            Uint128::from(5000u128)
+        );
+    }
+
+    #[test]
+    fn add_random_coin_reward_fail_with_invalid_amount_range() {
+        let mut deps = default_setup();
+
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::RandomCoin(RandomCoinReward {
+                label: "lucky aura".to_string(),
+                denom: "uaura".to_string(),
+                min: Uint128::from(1000u128), // min is greater than max
+                max: Uint128::from(10u128),
+                pool: Uint128::from(50000u128),
+                amount: None,
+                number: 10,
+                id: 1,
+                tier: None,
+            }),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &coins(50000u128, "uaura".to_string())),
             add_reward,
         )
         .unwrap_err();
         match res {
-            ContractError::Unauthorized {} => {}
+            ContractError::InvalidAmountRange {} => {}
             _ => panic!(),
         }
     }
 
     #[test]
-    fn add_reward_fail_with_wheel_activated() {
+    fn add_random_coin_reward_fail_with_insufficent_pool() {
         let mut deps = default_setup();
 
-        ADMIN_CONFIG
-            .save(
-                deps.as_mut().storage,
-                &AdminConfig {
-                    admin: Addr::unchecked(CREATOR),
-                    // set activate to true
-                    activate: true,
-                },
-            )
-            .unwrap();
-
         let add_reward = ExecuteMsg::AddReward {
-            reward: WheelReward::Text(TextReward {
-                label: "you lose".to_string(),
-                number: 100,
+            reward: WheelReward::RandomCoin(RandomCoinReward {
+                label: "lucky aura".to_string(),
+                denom: "uaura".to_string(),
+                min: Uint128::from(10u128),
+                max: Uint128::from(1000u128),
+                pool: Uint128::from(99u128), // required at least 100uaura for min amount of 10 items
+                amount: None,
+                number: 10,
                 id: 1,
+                tier: None,
             }),
         };
 
         let res = execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(CREATOR, &[]),
+            mock_info(CREATOR, &coins(99u128, "uaura".to_string())),
             add_reward,
         )
         .unwrap_err();
         match res {
-            ContractError::WheelActivated {} => {}
+            ContractError::InsufficentFund {} => {}
             _ => panic!(),
         }
     }
 
+    // Mint NFT
     #[test]
-    fn add_reward_fail_with_too_many_slots() {
+    fn add_mint_nft_reward_success() {
         let mut deps = default_setup();
 
-        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(65536); // rewards reach maximum capacity
-        for i in 0..wheel_rewards.capacity() {
-            wheel_rewards.push(WheelReward::Text(TextReward {
-                label: "you lose".to_string(),
-                number: 1,
-                id: (i as u32),
-            }));
-        }
-
-        WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(65536, wheel_rewards))
-            .unwrap();
+        // the wheel is minter of collection
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&MinterResponse {
+                    minter: Some(mock_env().contract.address.to_string()),
+                })
+                .unwrap(),
+            ))
+        });
 
         let add_reward = ExecuteMsg::AddReward {
-            reward: WheelReward::Text(TextReward {
-                label: "you lose".to_string(),
+            reward: WheelReward::MintNft(MintNftReward {
+                label: "BBB collection".to_string(),
+                collection_address: "cw721".to_string(),
+                token_id_prefix: "bbb-".to_string(),
+                token_uri: Some("ipfs://bbb/{token_id}.json".to_string()),
+                next_serial: 1,
                 number: 100,
-                id: u32::MAX,
+                id: 1,
+                tier: None,
             }),
         };
 
@@ -728,34 +1061,34 @@ mod unit_tests {
             mock_info(CREATOR, &[]),
             add_reward,
         )
-        .unwrap_err();
-        match res {
-            ContractError::TooManySlots {} => {}
-            _ => panic!(),
-        }
+        .unwrap();
+        assert_eq!(res, Response::new().add_attribute("action", "add_rewards"));
     }
 
     #[test]
-    fn add_reward_fail_with_too_many_rewards() {
+    fn add_mint_nft_reward_fail_with_not_minter() {
         let mut deps = default_setup();
 
-        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
-        // add reward
-        wheel_rewards.push(WheelReward::Text(TextReward {
-            label: "you lose".to_string(),
-            number: u32::MAX,
-            id: 1,
-        }));
-
-        WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(u32::MAX, wheel_rewards))
-            .unwrap();
+        // another address is minter of collection
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&MinterResponse {
+                    minter: Some(CREATOR.to_string()),
+                })
+                .unwrap(),
+            ))
+        });
 
         let add_reward = ExecuteMsg::AddReward {
-            reward: WheelReward::Text(TextReward {
-                label: "you lose".to_string(),
-                number: 1,
-                id: 2,
+            reward: WheelReward::MintNft(MintNftReward {
+                label: "BBB collection".to_string(),
+                collection_address: "cw721".to_string(),
+                token_id_prefix: "bbb-".to_string(),
+                token_uri: None,
+                next_serial: 1,
+                number: 100,
+                id: 1,
+                tier: None,
             }),
         };
 
@@ -767,315 +1100,395 @@ mod unit_tests {
         )
         .unwrap_err();
         match res {
-            ContractError::TooManyRewards {} => {}
+            ContractError::NotMinter {} => {}
             _ => panic!(),
         }
     }
 
-    /* ============================================================ RemoveReward ============================================================ */
+    // Contract call
     #[test]
-    fn remove_reward_success() {
+    fn add_contract_call_reward_success() {
         let mut deps = default_setup();
 
-        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
-        // add reward
-        wheel_rewards.push(WheelReward::Text(TextReward {
-            label: "you lose".to_string(),
-            number: 100,
-            id: 1,
-        }));
-
-        WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(100, wheel_rewards))
-            .unwrap();
+        let add_call_targets = ExecuteMsg::AddCallTargets {
+            addresses: vec!["launchpad".to_string()],
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            add_call_targets,
+        )
+        .unwrap();
 
-        let slot = 0;
-        let remove_reward = ExecuteMsg::RemoveReward { slot };
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::ContractCall(ContractCallReward {
+                label: "Guaranteed mint spot".to_string(),
+                contract_address: "launchpad".to_string(),
+                msg: r#"{"add_whitelist":{"addresses":["{winner}"]}}"#.to_string(),
+                number: 10,
+                id: 1,
+                tier: None,
+            }),
+        };
 
         let res = execute(
             deps.as_mut(),
             mock_env(),
             mock_info(CREATOR, &[]),
-            remove_reward,
+            add_reward,
         )
         .unwrap();
-        assert_eq!(
-            res,
-            Response::new()
-                .add_attribute("action", "remove_reward")
-                .add_attribute("slot", slot.to_string())
-        );
-
-        assert_eq!(
-            WHEEL_REWARDS.load(deps.as_mut().storage).unwrap(),
-            (0, vec![])
-        );
+        assert_eq!(res, Response::new().add_attribute("action", "add_rewards"));
     }
 
     #[test]
-    fn remove_reward_fail_with_unauthorized() {
+    fn add_contract_call_reward_fail_with_target_not_allowed() {
         let mut deps = default_setup();
 
-        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
-        // add reward
-        wheel_rewards.push(WheelReward::Text(TextReward {
-            label: "you lose".to_string(),
-            number: 100,
-            id: 1,
-        }));
-
-        WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(1, wheel_rewards))
-            .unwrap();
-
-        let slot = 0;
-        let remove_reward = ExecuteMsg::RemoveReward { slot };
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::ContractCall(ContractCallReward {
+                label: "Guaranteed mint spot".to_string(),
+                contract_address: "launchpad".to_string(),
+                msg: r#"{"add_whitelist":{"addresses":["{winner}"]}}"#.to_string(),
+                number: 10,
+                id: 1,
+                tier: None,
+            }),
+        };
 
         let res = execute(
             deps.as_mut(),
             mock_env(),
-            mock_info(USER /* sender is user */, &[]),
-            remove_reward,
+            mock_info(CREATOR, &[]),
+            add_reward,
         )
         .unwrap_err();
         match res {
-            ContractError::Unauthorized {} => {}
+            ContractError::CallTargetNotAllowed {} => {}
             _ => panic!(),
         }
     }
 
     #[test]
-    fn remove_reward_fail_with_wheel_activated() {
+    fn add_contract_call_reward_fail_with_invalid_msg() {
         let mut deps = default_setup();
 
-        ADMIN_CONFIG
-            .save(
-                deps.as_mut().storage,
-                &AdminConfig {
-                    admin: Addr::unchecked(CREATOR),
-                    // set activate to true
-                    activate: true,
-                },
-            )
-            .unwrap();
-
-        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
-        // add reward
-        wheel_rewards.push(WheelReward::Text(TextReward {
-            label: "you lose".to_string(),
-            number: 100,
-            id: 1,
-        }));
-
-        WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(1, wheel_rewards))
-            .unwrap();
+        let add_call_targets = ExecuteMsg::AddCallTargets {
+            addresses: vec!["launchpad".to_string()],
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            add_call_targets,
+        )
+        .unwrap();
 
-        let slot = 0;
-        let remove_reward = ExecuteMsg::RemoveReward { slot };
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::ContractCall(ContractCallReward {
+                label: "Guaranteed mint spot".to_string(),
+                contract_address: "launchpad".to_string(),
+                // missing closing brace
+                msg: r#"{"add_whitelist":{"addresses":["{winner}"]}"#.to_string(),
+                number: 10,
+                id: 1,
+                tier: None,
+            }),
+        };
 
         let res = execute(
             deps.as_mut(),
             mock_env(),
             mock_info(CREATOR, &[]),
-            remove_reward,
+            add_reward,
         )
         .unwrap_err();
         match res {
-            ContractError::WheelActivated {} => {}
+            ContractError::CustomError { val } => {
+                assert_eq!(val, "invalid contract call message")
+            }
             _ => panic!(),
         }
     }
 
+    // Voucher
     #[test]
-    fn remove_reward_fail_with_invalid_slot() {
+    fn add_voucher_reward_success() {
         let mut deps = default_setup();
 
-        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
-        // add reward
-        wheel_rewards.push(WheelReward::Text(TextReward {
-            label: "you lose".to_string(),
-            number: 100,
-            id: 1,
-        }));
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::Voucher(VoucherReward {
+                label: "Merch coupon".to_string(),
+                code_hashes: vec![
+                    hex::encode(Sha256::digest(b"CODE-1")),
+                    hex::encode(Sha256::digest(b"CODE-2")).to_uppercase(),
+                ],
+                next_index: 0,
+                id: 1,
+                tier: None,
+            }),
+        };
 
-        WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(1, wheel_rewards))
-            .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            add_reward,
+        )
+        .unwrap();
+        assert_eq!(res, Response::new().add_attribute("action", "add_rewards"));
 
-        let slot = 1;
-        let remove_reward = ExecuteMsg::RemoveReward { slot };
+        // code hashes are stored in lowercase
+        let (supply, wheel_rewards) = WHEEL_REWARDS.load(deps.as_ref().storage).unwrap();
+        assert_eq!(supply, 2);
+        match &wheel_rewards[0] {
+            WheelReward::Voucher(voucher) => {
+                assert_eq!(
+                    voucher.code_hashes[1],
+                    hex::encode(Sha256::digest(b"CODE-2"))
+                );
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn add_voucher_reward_fail_with_invalid_code_hash() {
+        let mut deps = default_setup();
+
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::Voucher(VoucherReward {
+                label: "Merch coupon".to_string(),
+                // plain code instead of its hash
+                code_hashes: vec!["CODE-1".to_string()],
+                next_index: 0,
+                id: 1,
+                tier: None,
+            }),
+        };
 
         let res = execute(
             deps.as_mut(),
             mock_env(),
             mock_info(CREATOR, &[]),
-            remove_reward,
+            add_reward,
         )
         .unwrap_err();
         match res {
-            ContractError::InvalidSlotReward {} => {}
+            ContractError::InvalidCodeHash {} => {}
             _ => panic!(),
         }
     }
 
-    /* ============================================================ ActivateWheel ============================================================ */
+    // Bundle
     #[test]
-    fn activate_wheel_success() {
+    fn add_bundle_reward_success() {
         let mut deps = default_setup();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
-
-        let activate_wheel = ExecuteMsg::ActivateWheel {
-            price: Coin {
-                denom: "uaura".to_string(),
-                amount: Uint128::from(1000u128),
-            },
-            start_time: Some(Timestamp::from_seconds(10000)),
-            end_time: Timestamp::from_seconds(20000),
-            shuffle: Some(true),
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::Bundle(BundleReward {
+                label: "mystery box".to_string(),
+                bundles: vec![Bundle {
+                    nfts: vec![NftAsset {
+                        collection_address: "cw721".to_string(),
+                        token_id: "111".to_string(),
+                    }],
+                    tokens: vec![TokenAsset {
+                        token_address: "cw20".to_string(),
+                        amount: Uint128::from(100u128),
+                    }],
+                    coins: coins(5u128, "uaura".to_string()),
+                }],
+                id: 1,
+                tier: None,
+            }),
         };
 
-        let res = execute(deps.as_mut(), env, mock_info(CREATOR, &[]), activate_wheel).unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &coins(5u128, "uaura".to_string())),
+            add_reward,
+        )
+        .unwrap();
+
+        let transfer_nft_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "cw721".to_string(),
+            msg: to_json_binary(
+                &CW721ExecuteMsg::<CW721Extension, CW721Extension>::TransferNft {
+                    recipient: mock_env().contract.address.to_string(),
+                    token_id: "111".to_string(),
+                },
+            )
+            .unwrap(),
+            funds: vec![],
+        });
+
+        let transfer_token_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "cw20".to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: CREATOR.to_string(),
+                recipient: mock_env().contract.address.to_string(),
+                amount: Uint128::from(100u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        });
+
         assert_eq!(
             res,
-            Response::new().add_attribute("action", "activate_wheel")
+            Response::new()
+                .add_attribute("action", "add_rewards")
+                .add_message(transfer_nft_msg)
+                .add_message(transfer_token_msg)
+        );
+
+        assert_eq!(
+            LOCKED_COINS
+                .load(deps.as_ref().storage, "uaura".to_string())
+                .unwrap(),
+            Uint128::from(5u128)
         );
     }
 
     #[test]
-    fn activate_wheel_fail_with_unauthorized() {
+    fn add_bundle_reward_fail_with_insufficent_fund() {
         let mut deps = default_setup();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
-
-        let activate_wheel = ExecuteMsg::ActivateWheel {
-            price: Coin {
-                denom: "uaura".to_string(),
-                amount: Uint128::from(1000u128),
-            },
-            start_time: Some(Timestamp::from_seconds(10000)),
-            end_time: Timestamp::from_seconds(20000),
-            shuffle: None,
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::Bundle(BundleReward {
+                label: "mystery box".to_string(),
+                bundles: vec![
+                    Bundle {
+                        nfts: vec![],
+                        tokens: vec![],
+                        coins: coins(5u128, "uaura".to_string()),
+                    },
+                    Bundle {
+                        nfts: vec![],
+                        tokens: vec![],
+                        coins: coins(5u128, "uaura".to_string()),
+                    },
+                ],
+                id: 1,
+                tier: None,
+            }),
         };
 
         let res = execute(
             deps.as_mut(),
-            env,
-            mock_info(USER /* sender is user */, &[]),
-            activate_wheel,
+            mock_env(),
+            mock_info(
+                CREATOR,
+                &coins(9u128, "uaura".to_string()), /* insufficent funds, required 10uaura */
+            ),
+            add_reward,
         )
         .unwrap_err();
         match res {
-            ContractError::Unauthorized {} => {}
+            ContractError::InsufficentFund {} => {}
             _ => panic!(),
         }
     }
 
     #[test]
-    fn activate_wheel_fail_with_wheel_activated() {
+    fn add_bundle_reward_fail_with_empty_bundle() {
         let mut deps = default_setup();
 
-        ADMIN_CONFIG
-            .save(
-                deps.as_mut().storage,
-                &AdminConfig {
-                    admin: Addr::unchecked(CREATOR),
-                    // set activate to true
-                    activate: true,
-                },
-            )
-            .unwrap();
-
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
-
-        let activate_wheel = ExecuteMsg::ActivateWheel {
-            price: Coin {
-                denom: "uaura".to_string(),
-                amount: Uint128::from(1000u128),
-            },
-            start_time: Some(Timestamp::from_seconds(10000)),
-            end_time: Timestamp::from_seconds(20000),
-            shuffle: None,
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::Bundle(BundleReward {
+                label: "mystery box".to_string(),
+                bundles: vec![Bundle {
+                    nfts: vec![],
+                    tokens: vec![],
+                    coins: vec![],
+                }],
+                id: 1,
+                tier: None,
+            }),
         };
 
-        let res = execute(deps.as_mut(), env, mock_info(CREATOR, &[]), activate_wheel).unwrap_err();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            add_reward,
+        )
+        .unwrap_err();
         match res {
-            ContractError::WheelActivated {} => {}
+            ContractError::InvalidBundle {} => {}
             _ => panic!(),
         }
     }
 
-    /* ============================================================ WithdrawCoin  ======================================================================== */
     #[test]
-    fn withdraw_native_coin_fail_with_insufficent_fund() {
+    fn add_bundle_reward_fail_with_overflow() {
         let mut deps = default_setup();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
-
-        let activate_wheel = ExecuteMsg::ActivateWheel {
-            price: Coin {
-                denom: "uaura".to_string(),
-                amount: Uint128::from(1000u128),
-            },
-            start_time: Some(Timestamp::from_seconds(10000)),
-            end_time: Timestamp::from_seconds(20000),
-            shuffle: None,
-        };
-
-        _ = execute(
-            deps.as_mut(),
-            env.clone(),
-            mock_info(CREATOR, &[]),
-            activate_wheel,
-        );
-
-        let withdraw_native_coin = ExecuteMsg::WithdrawCoin {
-            recipient: Some("recipient".to_string()),
-            denom: "uaura".to_string(),
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::Bundle(BundleReward {
+                label: "mystery box".to_string(),
+                bundles: vec![
+                    Bundle {
+                        nfts: vec![],
+                        tokens: vec![],
+                        coins: coins(u128::MAX, "uaura".to_string()),
+                    },
+                    Bundle {
+                        nfts: vec![],
+                        tokens: vec![],
+                        coins: coins(1u128, "uaura".to_string()),
+                    },
+                ],
+                id: 1,
+                tier: None,
+            }),
         };
 
-        let env = env_with_specify(Timestamp::from_seconds(21000), 1);
-
         let res = execute(
             deps.as_mut(),
-            env,
-            mock_info(CREATOR, &[]),
-            withdraw_native_coin,
+            mock_env(),
+            mock_info(CREATOR, &coins(u128::MAX, "uaura".to_string())),
+            add_reward,
         )
         .unwrap_err();
         match res {
-            ContractError::InsufficentFund {} => {}
+            ContractError::Std(StdError::Overflow { .. }) => {}
             _ => panic!(),
         }
     }
 
+    // general fail
     #[test]
-    fn withdraw_native_coin_fail_with_wheel_not_activated() {
+    fn add_reward_fail_with_unauthorized() {
         let mut deps = default_setup();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
-
-        let withdraw_native_coin = ExecuteMsg::WithdrawCoin {
-            recipient: Some("recipient".to_string()),
-            denom: "uaura".to_string(),
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::Text(TextReward {
+                label: "you lose".to_string(),
+                number: 100,
+                id: 1,
+                tier: None,
+                is_losing: false,
+            }),
         };
 
         let res = execute(
             deps.as_mut(),
-            env,
-            mock_info(CREATOR, &[]),
-            withdraw_native_coin,
+            mock_env(),
+            mock_info(USER /*sender is user*/, &[]),
+            add_reward,
         )
         .unwrap_err();
         match res {
-            ContractError::WheelNotActivated {} => {}
+            ContractError::Unauthorized {} => {}
             _ => panic!(),
         }
     }
 
     #[test]
-    fn withdraw_native_coin_fail_with_unauthorized() {
+    fn add_reward_fail_with_wheel_activated() {
         let mut deps = default_setup();
 
         ADMIN_CONFIG
@@ -1089,245 +1502,319 @@ mod unit_tests {
             )
             .unwrap();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
-
-        let withdraw_native_coin = ExecuteMsg::WithdrawCoin {
-            recipient: Some("recipient".to_string()),
-            denom: "uaura".to_string(),
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::Text(TextReward {
+                label: "you lose".to_string(),
+                number: 100,
+                id: 1,
+                tier: None,
+                is_losing: false,
+            }),
         };
 
         let res = execute(
             deps.as_mut(),
-            env,
-            mock_info(USER, &[]),
-            withdraw_native_coin,
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            add_reward,
         )
         .unwrap_err();
         match res {
-            ContractError::Unauthorized {} => {}
+            ContractError::WheelActivated {} => {}
             _ => panic!(),
         }
     }
 
     #[test]
-    fn withdraw_native_coin_fail_with_wheel_not_end() {
+    fn add_reward_fail_with_too_many_slots() {
         let mut deps = default_setup();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
-
-        let activate_wheel = ExecuteMsg::ActivateWheel {
-            price: Coin {
-                denom: "uaura".to_string(),
-                amount: Uint128::from(1000u128),
-            },
-            start_time: Some(Timestamp::from_seconds(10000)),
-            end_time: Timestamp::from_seconds(20000),
-            shuffle: None,
-        };
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(65536); // rewards reach maximum capacity
+        for i in 0..wheel_rewards.capacity() {
+            wheel_rewards.push(WheelReward::Text(TextReward {
+                label: "you lose".to_string(),
+                number: 1,
+                id: (i as u32),
+                tier: None,
+                is_losing: false,
+            }));
+        }
 
-        _ = execute(
-            deps.as_mut(),
-            env.clone(),
-            mock_info(CREATOR, &[]),
-            activate_wheel,
-        );
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(65536, wheel_rewards))
+            .unwrap();
 
-        let withdraw_native_coin = ExecuteMsg::WithdrawCoin {
-            recipient: Some("recipient".to_string()),
-            denom: "uaura".to_string(),
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::Text(TextReward {
+                label: "you lose".to_string(),
+                number: 100,
+                id: u32::MAX,
+                tier: None,
+                is_losing: false,
+            }),
         };
 
-        let env = env_with_specify(Timestamp::from_seconds(19000) /* < 20000 */, 1);
-
         let res = execute(
             deps.as_mut(),
-            env,
+            mock_env(),
             mock_info(CREATOR, &[]),
-            withdraw_native_coin,
+            add_reward,
         )
         .unwrap_err();
         match res {
-            ContractError::WheelNotEnded {} => {}
+            ContractError::TooManySlots {} => {}
             _ => panic!(),
         }
     }
 
-    /* ============================================================ Spin  ======================================================================== */
     #[test]
-    fn spin_fail_with_wheel_not_activated() {
+    fn add_reward_fail_with_too_many_rewards() {
         let mut deps = default_setup();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: u32::MAX,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
 
-        let spin_msg = ExecuteMsg::Spin { number: Some(5) };
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(u32::MAX, wheel_rewards))
+            .unwrap();
 
-        let res = execute(deps.as_mut(), env, mock_info(USER, &[]), spin_msg).unwrap_err();
+        let add_reward = ExecuteMsg::AddReward {
+            reward: WheelReward::Text(TextReward {
+                label: "you lose".to_string(),
+                number: 1,
+                id: 2,
+                tier: None,
+                is_losing: false,
+            }),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            add_reward,
+        )
+        .unwrap_err();
         match res {
-            ContractError::WheelNotActivated {} => {}
+            ContractError::TooManyRewards {} => {}
             _ => panic!(),
         }
     }
 
+    /* ============================================================ RemoveReward ============================================================ */
     #[test]
-    fn spin_fail_with_spin_number_larger_than_config() {
+    fn remove_reward_success() {
         let mut deps = default_setup();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: 100,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
 
-        let activate_wheel = ExecuteMsg::ActivateWheel {
-            price: Coin {
-                denom: "uaura".to_string(),
-                amount: Uint128::from(1000u128),
-            },
-            start_time: Some(Timestamp::from_seconds(10000)),
-            end_time: Timestamp::from_seconds(40000),
-            shuffle: None,
-        };
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .unwrap();
 
-        _ = execute(
+        let slot = 0;
+        let remove_reward = ExecuteMsg::RemoveReward { slot };
+
+        let res = execute(
             deps.as_mut(),
-            env.clone(),
+            mock_env(),
             mock_info(CREATOR, &[]),
-            activate_wheel,
+            remove_reward,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "remove_reward")
+                .add_attribute("slot", slot.to_string())
         );
 
-        let spin_msg = ExecuteMsg::Spin {
-            number: Some(101), /* max is 10 */
-        };
-
-        let res = execute(deps.as_mut(), env, mock_info(USER, &[]), spin_msg).unwrap_err();
-        match res {
-            ContractError::InvalidNumberSpins {} => {}
-            _ => panic!(),
-        }
+        assert_eq!(
+            WHEEL_REWARDS.load(deps.as_mut().storage).unwrap(),
+            (0, vec![])
+        );
     }
 
     #[test]
-    fn spin_fail_with_start_time_larger_than_current() {
+    fn remove_reward_fail_with_unauthorized() {
         let mut deps = default_setup();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
-
-        let activate_wheel = ExecuteMsg::ActivateWheel {
-            price: Coin {
-                denom: "uaura".to_string(),
-                amount: Uint128::from(1000u128),
-            },
-            start_time: Some(Timestamp::from_seconds(20000) /* > 15000 */),
-            end_time: Timestamp::from_seconds(40000),
-            shuffle: None,
-        };
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: 100,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
 
-        _ = execute(
-            deps.as_mut(),
-            env.clone(),
-            mock_info(CREATOR, &[]),
-            activate_wheel,
-        );
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(1, wheel_rewards))
+            .unwrap();
 
-        let spin_msg = ExecuteMsg::Spin { number: Some(11) };
+        let slot = 0;
+        let remove_reward = ExecuteMsg::RemoveReward { slot };
 
-        let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), spin_msg).unwrap_err();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER /* sender is user */, &[]),
+            remove_reward,
+        )
+        .unwrap_err();
         match res {
-            ContractError::InvalidNumberSpins {} => {}
+            ContractError::Unauthorized {} => {}
             _ => panic!(),
         }
     }
 
     #[test]
-    fn spin_fail_with_current_larger_than_end_time() {
+    fn remove_reward_fail_with_wheel_activated() {
         let mut deps = default_setup();
 
+        ADMIN_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &AdminConfig {
+                    admin: Addr::unchecked(CREATOR),
+                    // set activate to true
+                    activate: true,
+                },
+            )
+            .unwrap();
+
         let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
         // add reward
         wheel_rewards.push(WheelReward::Text(TextReward {
             label: "you lose".to_string(),
             number: 100,
             id: 1,
+            tier: None,
+            is_losing: false,
         }));
 
         WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .save(deps.as_mut().storage, &(1, wheel_rewards))
             .unwrap();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
-
-        let activate_wheel = ExecuteMsg::ActivateWheel {
-            price: Coin {
-                denom: "uaura".to_string(),
-                amount: Uint128::from(1000u128),
-            },
-            start_time: Some(Timestamp::from_seconds(10000)),
-            end_time: Timestamp::from_seconds(40000),
-            shuffle: None,
-        };
+        let slot = 0;
+        let remove_reward = ExecuteMsg::RemoveReward { slot };
 
-        _ = execute(
+        let res = execute(
             deps.as_mut(),
-            env.clone(),
+            mock_env(),
             mock_info(CREATOR, &[]),
-            activate_wheel,
-        );
-
-        let env = env_with_specify(Timestamp::from_seconds(50000) /* > 40000 */, 1);
-
-        let spin_msg = ExecuteMsg::Spin { number: Some(1) };
-
-        let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), spin_msg).unwrap_err();
+            remove_reward,
+        )
+        .unwrap_err();
         match res {
-            ContractError::WheelEnded {} => {}
+            ContractError::WheelActivated {} => {}
             _ => panic!(),
         }
     }
 
     #[test]
-    fn spin_fail_with_spin_amount_not_enough() {
+    fn remove_reward_fail_with_invalid_slot() {
         let mut deps = default_setup();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
-
         let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
         // add reward
         wheel_rewards.push(WheelReward::Text(TextReward {
             label: "you lose".to_string(),
             number: 100,
             id: 1,
+            tier: None,
+            is_losing: false,
         }));
 
         WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .save(deps.as_mut().storage, &(1, wheel_rewards))
             .unwrap();
 
-        let activate_wheel = ExecuteMsg::ActivateWheel {
-            price: Coin {
-                denom: "uaura".to_string(),
-                amount: Uint128::from(1000u128),
-            },
-            start_time: Some(Timestamp::from_seconds(10000)),
-            end_time: Timestamp::from_seconds(40000),
-            shuffle: None,
-        };
+        let slot = 1;
+        let remove_reward = ExecuteMsg::RemoveReward { slot };
 
-        _ = execute(
+        let res = execute(
             deps.as_mut(),
-            env.clone(),
+            mock_env(),
             mock_info(CREATOR, &[]),
-            activate_wheel,
+            remove_reward,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InvalidSlotReward {} => {}
+            _ => panic!(),
+        }
+    }
+
+    /* ============================================================ SetJackpot ============================================================ */
+    #[test]
+    fn set_jackpot_success() {
+        let mut deps = default_setup();
+
+        let set_jackpot = ExecuteMsg::SetJackpot {
+            label: "jackpot".to_string(),
+            seed: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(10000u128),
+            },
+            contribution_rate: Decimal::percent(10),
+            probability: Decimal::permille(1),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &coins(10000u128, "uaura".to_string())),
+            set_jackpot,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "set_jackpot")
+                .add_attribute("pool", "10000")
         );
+    }
 
-        let spin_msg = ExecuteMsg::Spin { number: Some(1) };
+    #[test]
+    fn set_jackpot_fail_with_insufficent_fund() {
+        let mut deps = default_setup();
+
+        let set_jackpot = ExecuteMsg::SetJackpot {
+            label: "jackpot".to_string(),
+            seed: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(10000u128),
+            },
+            contribution_rate: Decimal::percent(10),
+            probability: Decimal::permille(1),
+        };
 
         let res = execute(
             deps.as_mut(),
-            env.clone(),
+            mock_env(),
             mock_info(
-                USER,
-                &[Coin {
-                    denom: "uaura".to_string(),
-                    amount: Uint128::from(999u128 /* Not enough fund */),
-                }],
+                CREATOR,
+                &coins(9999u128, "uaura".to_string()), /* insufficent funds, required 10000uaura */
             ),
-            spin_msg,
+            set_jackpot,
         )
         .unwrap_err();
         match res {
@@ -1337,81 +1824,148 @@ mod unit_tests {
     }
 
     #[test]
-    fn spin_success() {
+    fn fund_jackpot_fail_with_jackpot_not_found() {
         let mut deps = default_setup();
 
-        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &coins(10000u128, "uaura".to_string())),
+            ExecuteMsg::FundJackpot {},
+        )
+        .unwrap_err();
+        match res {
+            ContractError::JackpotNotFound {} => {}
+            _ => panic!(),
+        }
+    }
 
-        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
-        // add reward
+    /* ============================================================ SetTiers ============================================================ */
+    #[test]
+    fn set_tiers_success() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(3);
+        // add rewards
         wheel_rewards.push(WheelReward::Text(TextReward {
-            label: "you lose".to_string(),
+            label: "common".to_string(),
             number: 100,
             id: 1,
+            tier: Some("common".to_string()),
+            is_losing: false,
+        }));
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "common".to_string(),
+            number: 100,
+            id: 2,
+            tier: Some("common".to_string()),
+            is_losing: false,
+        }));
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "legendary".to_string(),
+            number: 1,
+            id: 3,
+            tier: Some("legendary".to_string()),
+            is_losing: false,
         }));
 
         WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .save(deps.as_mut().storage, &(201, wheel_rewards))
             .unwrap();
 
-        let activate_wheel = ExecuteMsg::ActivateWheel {
-            price: Coin {
-                denom: "uaura".to_string(),
-                amount: Uint128::from(1000u128),
-            },
-            start_time: Some(Timestamp::from_seconds(10000)),
-            end_time: Timestamp::from_seconds(40000),
-            shuffle: None,
+        let set_tiers = ExecuteMsg::SetTiers {
+            tiers: vec![
+                Tier {
+                    name: "common".to_string(),
+                    weight: 90,
+                },
+                Tier {
+                    name: "rare".to_string(),
+                    weight: 9,
+                },
+                Tier {
+                    name: "legendary".to_string(),
+                    weight: 1,
+                },
+            ],
         };
 
-        _ = execute(
-            deps.as_mut(),
-            env.clone(),
-            mock_info(CREATOR, &[]),
-            activate_wheel,
-        );
-
-        let spin_msg = ExecuteMsg::Spin { number: Some(1) };
-
         let res = execute(
             deps.as_mut(),
-            env.clone(),
-            mock_info(
-                USER,
-                &[Coin {
-                    denom: "uaura".to_string(),
-                    amount: Uint128::from(1000u128),
-                }],
-            ),
-            spin_msg,
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            set_tiers,
         )
         .unwrap();
         assert_eq!(
             res,
             Response::new()
-                .add_attribute("action", "spin")
-                .add_attribute("sender", USER)
-                .add_attribute("spun", "0")
-                .add_attribute("spins", "1")
+                .add_attribute("action", "set_tiers")
+                .add_attribute("tiers", "3")
+        );
+
+        // empty tier has no chance, the others share the odds by weight
+        let tiers: Vec<TierResponse> =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::GetTiers {}).unwrap()).unwrap();
+        assert_eq!(
+            tiers,
+            vec![
+                TierResponse {
+                    name: "common".to_string(),
+                    weight: 90,
+                    supply: 200,
+                    odds: Decimal::from_ratio(90u32, 91u32),
+                },
+                TierResponse {
+                    name: "rare".to_string(),
+                    weight: 9,
+                    supply: 0,
+                    odds: Decimal::zero(),
+                },
+                TierResponse {
+                    name: "legendary".to_string(),
+                    weight: 1,
+                    supply: 1,
+                    odds: Decimal::from_ratio(1u32, 91u32),
+                },
+            ]
         );
     }
 
-    /* ============================================================ Withdraw  ======================================================================== */
     #[test]
-    fn withdraw_reward_success() {
+    fn set_tiers_fail_with_duplicated_name() {
         let mut deps = default_setup();
 
-        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
-        // add reward
-        wheel_rewards.push(WheelReward::Text(TextReward {
-            label: "you lose".to_string(),
-            number: 100,
-            id: 1,
-        }));
+        let set_tiers = ExecuteMsg::SetTiers {
+            tiers: vec![
+                Tier {
+                    name: "common".to_string(),
+                    weight: 90,
+                },
+                Tier {
+                    name: "common".to_string(), // duplicated name
+                    weight: 10,
+                },
+            ],
+        };
 
-        WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(100, wheel_rewards))
-            .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            set_tiers,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InvalidTier {} => {}
+            _ => panic!(),
+        }
+    }
+
+    /* ============================================================ ActivateWheel ============================================================ */
+    #[test]
+    fn activate_wheel_success() {
+        let mut deps = default_setup();
 
         let env = env_with_specify(Timestamp::from_seconds(15000), 1);
 
@@ -1422,65 +1976,47 @@ mod unit_tests {
             },
             start_time: Some(Timestamp::from_seconds(10000)),
             end_time: Timestamp::from_seconds(20000),
-            shuffle: None,
-        };
-
-        _ = execute(
-            deps.as_mut(),
-            env.clone(),
-            mock_info(CREATOR, &[]),
-            activate_wheel,
-        );
-
-        let withdraw_reward = ExecuteMsg::Withdraw {
-            recipient: Some("recipient".to_string()),
-            slot: 0,
+            shuffle: Some(true),
         };
 
-        let env = env_with_specify(Timestamp::from_seconds(21000), 1);
-
-        let res = execute(deps.as_mut(), env, mock_info(CREATOR, &[]), withdraw_reward).unwrap();
+        let res = execute(deps.as_mut(), env, mock_info(CREATOR, &[]), activate_wheel).unwrap();
         assert_eq!(
             res,
-            Response::new()
-                .add_attribute("action", "withdraw")
-                .add_attribute("slot", "0")
-        )
+            Response::new().add_attribute("action", "activate_wheel")
+        );
     }
 
     #[test]
-    fn withdraw_reward_fail_with_wheel_not_activated() {
+    fn activate_wheel_fail_with_unauthorized() {
         let mut deps = default_setup();
 
-        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
-        // add reward
-        wheel_rewards.push(WheelReward::Text(TextReward {
-            label: "you lose".to_string(),
-            number: 100,
-            id: 1,
-        }));
-
-        WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(100, wheel_rewards))
-            .unwrap();
-
         let env = env_with_specify(Timestamp::from_seconds(15000), 1);
 
-        let withdraw_reward = ExecuteMsg::Withdraw {
-            recipient: Some("recipient".to_string()),
-            slot: 0,
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
         };
 
-        let res =
-            execute(deps.as_mut(), env, mock_info(CREATOR, &[]), withdraw_reward).unwrap_err();
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER /* sender is user */, &[]),
+            activate_wheel,
+        )
+        .unwrap_err();
         match res {
-            ContractError::WheelNotActivated {} => {}
+            ContractError::Unauthorized {} => {}
             _ => panic!(),
         }
     }
 
     #[test]
-    fn withdraw_reward_fail_with_unauthorized() {
+    fn activate_wheel_fail_with_wheel_activated() {
         let mut deps = default_setup();
 
         ADMIN_CONFIG
@@ -1494,48 +2030,53 @@ mod unit_tests {
             )
             .unwrap();
 
-        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
-        // add reward
-        wheel_rewards.push(WheelReward::Text(TextReward {
-            label: "you lose".to_string(),
-            number: 100,
-            id: 1,
-        }));
-
-        WHEEL_REWARDS
-            .save(deps.as_mut().storage, &(100, wheel_rewards))
-            .unwrap();
-
         let env = env_with_specify(Timestamp::from_seconds(15000), 1);
 
-        let withdraw_reward = ExecuteMsg::Withdraw {
-            recipient: Some("recipient".to_string()),
-            slot: 0,
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
         };
 
-        let res = execute(deps.as_mut(), env, mock_info(USER, &[]), withdraw_reward).unwrap_err();
+        let res = execute(deps.as_mut(), env, mock_info(CREATOR, &[]), activate_wheel).unwrap_err();
         match res {
-            ContractError::Unauthorized {} => {}
+            ContractError::WheelActivated {} => {}
             _ => panic!(),
         }
     }
 
     #[test]
-    fn withdraw_reward_fail_with_wheel_not_end() {
+    fn activate_wheel_fail_with_invalid_tier() {
         let mut deps = default_setup();
 
         let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
-        // add reward
+        // add reward without tier
         wheel_rewards.push(WheelReward::Text(TextReward {
             label: "you lose".to_string(),
             number: 100,
             id: 1,
+            tier: None,
+            is_losing: false,
         }));
 
         WHEEL_REWARDS
             .save(deps.as_mut().storage, &(100, wheel_rewards))
             .unwrap();
 
+        TIERS
+            .save(
+                deps.as_mut().storage,
+                &vec![Tier {
+                    name: "common".to_string(),
+                    weight: 1,
+                }],
+            )
+            .unwrap();
+
         let env = env_with_specify(Timestamp::from_seconds(15000), 1);
 
         let activate_wheel = ExecuteMsg::ActivateWheel {
@@ -1548,25 +2089,4432 @@ mod unit_tests {
             shuffle: None,
         };
 
-        _ = execute(
-            deps.as_mut(),
-            env.clone(),
-            mock_info(CREATOR, &[]),
-            activate_wheel,
-        );
+        let res = execute(deps.as_mut(), env, mock_info(CREATOR, &[]), activate_wheel).unwrap_err();
+        match res {
+            ContractError::InvalidTier {} => {}
+            _ => panic!(),
+        }
+    }
 
-        let withdraw_reward = ExecuteMsg::Withdraw {
-            recipient: Some("recipient".to_string()),
-            slot: 0,
+    /* ============================================================ WithdrawCoin  ======================================================================== */
+    #[test]
+    fn withdraw_native_coin_fail_with_insufficent_fund() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let withdraw_native_coin = ExecuteMsg::WithdrawCoin {
+            recipient: Some("recipient".to_string()),
+            denom: "uaura".to_string(),
+        };
+
+        let env = env_with_specify(Timestamp::from_seconds(21000), 1);
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info(CREATOR, &[]),
+            withdraw_native_coin,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InsufficentFund {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn withdraw_native_coin_fail_with_wheel_not_activated() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let withdraw_native_coin = ExecuteMsg::WithdrawCoin {
+            recipient: Some("recipient".to_string()),
+            denom: "uaura".to_string(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info(CREATOR, &[]),
+            withdraw_native_coin,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::WheelNotActivated {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn withdraw_native_coin_fail_with_unauthorized() {
+        let mut deps = default_setup();
+
+        ADMIN_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &AdminConfig {
+                    admin: Addr::unchecked(CREATOR),
+                    // set activate to true
+                    activate: true,
+                },
+            )
+            .unwrap();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let withdraw_native_coin = ExecuteMsg::WithdrawCoin {
+            recipient: Some("recipient".to_string()),
+            denom: "uaura".to_string(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            withdraw_native_coin,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn withdraw_native_coin_fail_with_wheel_not_end() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let withdraw_native_coin = ExecuteMsg::WithdrawCoin {
+            recipient: Some("recipient".to_string()),
+            denom: "uaura".to_string(),
         };
 
         let env = env_with_specify(Timestamp::from_seconds(19000) /* < 20000 */, 1);
 
-        let res =
-            execute(deps.as_mut(), env, mock_info(CREATOR, &[]), withdraw_reward).unwrap_err();
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info(CREATOR, &[]),
+            withdraw_native_coin,
+        )
+        .unwrap_err();
         match res {
             ContractError::WheelNotEnded {} => {}
             _ => panic!(),
         }
     }
+
+    /* ============================================================ Spin  ======================================================================== */
+    #[test]
+    fn spin_fail_with_wheel_not_activated() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let spin_msg = ExecuteMsg::Spin {
+            number: Some(5),
+            referrer: None,
+        };
+
+        let res = execute(deps.as_mut(), env, mock_info(USER, &[]), spin_msg).unwrap_err();
+        match res {
+            ContractError::WheelNotActivated {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn spin_fail_with_spin_number_larger_than_config() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(40000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let spin_msg = ExecuteMsg::Spin {
+            number: Some(101), /* max is 10 */
+            referrer: None,
+        };
+
+        let res = execute(deps.as_mut(), env, mock_info(USER, &[]), spin_msg).unwrap_err();
+        match res {
+            ContractError::InvalidNumberSpins {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn spin_fail_with_start_time_larger_than_current() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(20000) /* > 15000 */),
+            end_time: Timestamp::from_seconds(40000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let spin_msg = ExecuteMsg::Spin {
+            number: Some(11),
+            referrer: None,
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), spin_msg).unwrap_err();
+        match res {
+            ContractError::InvalidNumberSpins {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn spin_fail_with_current_larger_than_end_time() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: 100,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .unwrap();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(40000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let env = env_with_specify(Timestamp::from_seconds(50000) /* > 40000 */, 1);
+
+        let spin_msg = ExecuteMsg::Spin {
+            number: Some(1),
+            referrer: None,
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), spin_msg).unwrap_err();
+        match res {
+            ContractError::WheelEnded {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn spin_fail_with_spin_amount_not_enough() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: 100,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(40000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let spin_msg = ExecuteMsg::Spin {
+            number: Some(1),
+            referrer: None,
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(
+                USER,
+                &[Coin {
+                    denom: "uaura".to_string(),
+                    amount: Uint128::from(999u128 /* Not enough fund */),
+                }],
+            ),
+            spin_msg,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InsufficentFund {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn spin_success() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: 100,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(40000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let spin_msg = ExecuteMsg::Spin {
+            number: Some(1),
+            referrer: None,
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(
+                USER,
+                &[Coin {
+                    denom: "uaura".to_string(),
+                    amount: Uint128::from(1000u128),
+                }],
+            ),
+            spin_msg,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "spin")
+                .add_attribute("sender", USER)
+                .add_attribute("spun", "0")
+                .add_attribute("spins", "1")
+                .add_event(
+                    Event::new("spin")
+                        .add_attribute("player", USER)
+                        .add_attribute("spins", "1")
+                        .add_attribute("free_spins", "0")
+                        .add_attribute("payment", "1000uaura")
+                )
+                .add_event(
+                    Event::new("reward_won")
+                        .add_attribute("player", USER)
+                        .add_attribute("spin_id", "1")
+                        .add_attribute("index", "0")
+                        .add_attribute("slot_id", "1")
+                        .add_attribute("label", "you lose")
+                        .add_attribute("kind", "text")
+                )
+                .set_data(
+                    to_json_binary(&SpinResponse {
+                        player: Addr::unchecked(USER),
+                        job_id: None,
+                        rewards: vec![(
+                            0,
+                            WheelReward::Text(TextReward {
+                                label: "you lose".to_string(),
+                                number: 1,
+                                id: 1,
+                                tier: None,
+                                is_losing: false,
+                            })
+                        )],
+                    })
+                    .unwrap()
+                )
+        );
+    }
+
+    #[test]
+    fn spin_success_with_pity_threshold() {
+        let mut deps = default_setup();
+
+        let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+        config.pity_threshold = Some(2);
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(2);
+        // add losing reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: 1000,
+            id: 1,
+            tier: None,
+            is_losing: true,
+        }));
+        // add winning reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "t-shirt".to_string(),
+            number: 1,
+            id: 2,
+            tier: None,
+            is_losing: false,
+        }));
+
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(1001, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(40000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let spin_msg = ExecuteMsg::Spin {
+            number: Some(3),
+            referrer: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER, &coins(3000u128, "uaura".to_string())),
+            spin_msg,
+        )
+        .unwrap();
+
+        // the winning reward is guaranteed after 2 losing spins
+        let spins_result = player_rewards(&deps, USER);
+        let won_idx = spins_result
+            .iter()
+            .position(|(_, reward)| !reward.is_losing())
+            .unwrap();
+
+        let losing_streak: u32 = from_json(
+            query(
+                deps.as_ref(),
+                env,
+                QueryMsg::GetPlayerLosingStreak {
+                    address: USER.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(losing_streak as usize, spins_result.len() - won_idx - 1);
+    }
+
+    #[test]
+    fn spin_success_with_jackpot() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: 100,
+            id: 1,
+            tier: None,
+            is_losing: true,
+        }));
+
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .unwrap();
+
+        let set_jackpot = ExecuteMsg::SetJackpot {
+            label: "jackpot".to_string(),
+            seed: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(10000u128),
+            },
+            contribution_rate: Decimal::percent(10),
+            probability: Decimal::one(), // always win
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &coins(10000u128, "uaura".to_string())),
+            set_jackpot,
+        )
+        .unwrap();
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &coins(5000u128, "uaura".to_string())),
+            ExecuteMsg::FundJackpot {},
+        )
+        .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(40000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let spin_msg = ExecuteMsg::Spin {
+            number: Some(1),
+            referrer: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &coins(1000u128, "uaura".to_string())),
+            spin_msg,
+        )
+        .unwrap();
+
+        // player won the pool grown by 10% of the spin payment
+        let spins_result = player_rewards(&deps, USER);
+        assert_eq!(
+            spins_result[1],
+            (
+                false,
+                WheelReward::Coin(CoinReward {
+                    label: "jackpot".to_string(),
+                    coin: Coin {
+                        denom: "uaura".to_string(),
+                        amount: Uint128::from(10100u128),
+                    },
+                    number: 1,
+                    id: u32::MAX,
+                    tier: None,
+                    vesting: None,
+                })
+            )
+        );
+        assert_eq!(
+            LOCKED_COINS
+                .load(deps.as_ref().storage, "uaura".to_string())
+                .unwrap(),
+            // won pool and the reseeded pool
+            Uint128::from(15100u128)
+        );
+
+        // pool is reseeded from the reserve
+        let jackpot = JACKPOT.load(deps.as_ref().storage).unwrap();
+        assert_eq!(jackpot.pool, Uint128::from(5000u128));
+        assert_eq!(jackpot.reserve, Uint128::zero());
+    }
+
+    #[test]
+    fn spin_success_with_random_coin() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::RandomCoin(RandomCoinReward {
+            label: "lucky aura".to_string(),
+            denom: "uaura".to_string(),
+            min: Uint128::from(10u128),
+            max: Uint128::from(1000u128),
+            pool: Uint128::from(1030u128),
+            amount: None,
+            number: 3,
+            id: 1,
+            tier: None,
+        }));
+
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(3, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(40000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let spin_msg = ExecuteMsg::Spin {
+            number: Some(3),
+            referrer: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &coins(3000u128, "uaura".to_string())),
+            spin_msg,
+        )
+        .unwrap();
+
+        // every drawn amount is in range and the pool is never over-committed
+        let spins_result = player_rewards(&deps, USER);
+        let mut total = Uint128::zero();
+        for (_, reward) in spins_result {
+            match reward {
+                WheelReward::RandomCoin(coin) => {
+                    let amount = coin.amount.unwrap();
+                    assert!(amount >= coin.min && amount <= coin.max);
+                    assert_eq!(coin.pool, Uint128::zero());
+                    total += amount;
+                }
+                _ => panic!(),
+            }
+        }
+
+        let (_, wheel_rewards) = WHEEL_REWARDS.load(deps.as_ref().storage).unwrap();
+        match &wheel_rewards[0] {
+            WheelReward::RandomCoin(coin) => {
+                assert_eq!(coin.number, 0);
+                assert_eq!(coin.pool + total, Uint128::from(1030u128));
+            }
+            _ => panic!(),
+        }
+    }
+
+    /* ============================================================ ClaimReward  ======================================================================== */
+    #[test]
+    fn claim_bundle_reward_success() {
+        let mut deps = default_setup();
+
+        ADMIN_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &AdminConfig {
+                    admin: Addr::unchecked(CREATOR),
+                    // set activate to true
+                    activate: true,
+                },
+            )
+            .unwrap();
+
+        let bundle = Bundle {
+            nfts: vec![NftAsset {
+                collection_address: "cw721".to_string(),
+                token_id: "111".to_string(),
+            }],
+            tokens: vec![TokenAsset {
+                token_address: "cw20".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+            coins: coins(5u128, "uaura".to_string()),
+        };
+
+        // player won the bundle
+        save_won_reward(
+            &mut deps,
+            USER,
+            WheelReward::Bundle(BundleReward {
+                label: "mystery box".to_string(),
+                bundles: vec![bundle],
+                id: 1,
+                tier: None,
+            }),
+        );
+
+        LOCKED_COINS
+            .save(
+                deps.as_mut().storage,
+                "uaura".to_string(),
+                &Uint128::from(5u128),
+            )
+            .unwrap();
+
+        let claim_reward = ExecuteMsg::ClaimReward { rewards: vec![0] };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            claim_reward,
+        )
+        .unwrap();
+
+        let transfer_nft_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "cw721".to_string(),
+            msg: to_json_binary(
+                &CW721ExecuteMsg::<CW721Extension, CW721Extension>::TransferNft {
+                    recipient: USER.to_string(),
+                    token_id: "111".to_string(),
+                },
+            )
+            .unwrap(),
+            funds: vec![],
+        });
+
+        let transfer_token_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "cw20".to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: USER.to_string(),
+                amount: Uint128::from(100u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        });
+
+        let send_coin_msg: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: USER.to_string(),
+            amount: coins(5u128, "uaura".to_string()),
+        });
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "claim_reward")
+                .add_attribute("sender", USER)
+                .add_event(
+                    Event::new("claim")
+                        .add_attribute("player", USER)
+                        .add_attribute("index", "0")
+                        .add_attribute("slot_id", "1")
+                        .add_attribute("label", "mystery box")
+                        .add_attribute("kind", "bundle")
+                        .add_attribute("asset", "cw721")
+                        .add_attribute("amount", "1")
+                        .add_attribute("asset", "cw20")
+                        .add_attribute("amount", "100")
+                        .add_attribute("asset", "uaura")
+                        .add_attribute("amount", "5")
+                        .add_attribute("recipient", USER)
+                )
+                .add_message(transfer_nft_msg)
+                .add_message(transfer_token_msg)
+                .add_message(send_coin_msg)
+        );
+
+        assert!(!LOCKED_COINS.has(deps.as_ref().storage, "uaura".to_string()));
+    }
+
+    #[test]
+    fn claim_mint_nft_reward_success() {
+        let mut deps = default_setup();
+
+        ADMIN_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &AdminConfig {
+                    admin: Addr::unchecked(CREATOR),
+                    // set activate to true
+                    activate: true,
+                },
+            )
+            .unwrap();
+
+        // player won the nft of serial 7
+        save_won_reward(
+            &mut deps,
+            USER,
+            WheelReward::MintNft(MintNftReward {
+                label: "BBB collection".to_string(),
+                collection_address: "cw721".to_string(),
+                token_id_prefix: "bbb-".to_string(),
+                token_uri: Some("ipfs://bbb/{token_id}.json".to_string()),
+                next_serial: 7,
+                number: 1,
+                id: 1,
+                tier: None,
+            }),
+        );
+
+        let claim_reward = ExecuteMsg::ClaimReward { rewards: vec![0] };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            claim_reward,
+        )
+        .unwrap();
+
+        let mint_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "cw721".to_string(),
+            msg: to_json_binary(&CW721ExecuteMsg::<CW721Extension, Empty>::Mint {
+                token_id: "bbb-7".to_string(),
+                owner: USER.to_string(),
+                token_uri: Some("ipfs://bbb/bbb-7.json".to_string()),
+                extension: None,
+            })
+            .unwrap(),
+            funds: vec![],
+        });
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "claim_reward")
+                .add_attribute("sender", USER)
+                .add_event(
+                    Event::new("claim")
+                        .add_attribute("player", USER)
+                        .add_attribute("index", "0")
+                        .add_attribute("slot_id", "1")
+                        .add_attribute("label", "BBB collection")
+                        .add_attribute("kind", "mint_nft")
+                        .add_attribute("asset", "cw721")
+                        .add_attribute("amount", "1")
+                        .add_attribute("recipient", USER)
+                )
+                .add_message(mint_msg)
+        );
+    }
+
+    #[test]
+    fn claim_contract_call_reward_success() {
+        let mut deps = default_setup();
+
+        ADMIN_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &AdminConfig {
+                    admin: Addr::unchecked(CREATOR),
+                    // set activate to true
+                    activate: true,
+                },
+            )
+            .unwrap();
+
+        save_won_reward(
+            &mut deps,
+            USER,
+            WheelReward::ContractCall(ContractCallReward {
+                label: "Guaranteed mint spot".to_string(),
+                contract_address: "launchpad".to_string(),
+                msg: r#"{"add_whitelist":{"addresses":["{winner}"]}}"#.to_string(),
+                number: 1,
+                id: 1,
+                tier: None,
+            }),
+        );
+
+        let claim_reward = ExecuteMsg::ClaimReward { rewards: vec![0] };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            claim_reward,
+        )
+        .unwrap();
+
+        // winner address is templated into the message
+        let call_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "launchpad".to_string(),
+            msg: Binary::from(r#"{"add_whitelist":{"addresses":["user"]}}"#.as_bytes()),
+            funds: vec![],
+        });
+
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "claim_reward")
+                .add_attribute("sender", USER)
+                .add_event(
+                    Event::new("claim")
+                        .add_attribute("player", USER)
+                        .add_attribute("index", "0")
+                        .add_attribute("slot_id", "1")
+                        .add_attribute("label", "Guaranteed mint spot")
+                        .add_attribute("kind", "contract_call")
+                        .add_attribute("asset", "launchpad")
+                        .add_attribute("amount", "1")
+                        .add_attribute("recipient", USER)
+                )
+                .add_message(call_msg)
+        );
+    }
+
+    /* ============================================================ ClaimVested  ======================================================================== */
+    #[test]
+    fn claim_vested_success() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(10000), 1);
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward with 200 seconds cliff and 1000 seconds vesting
+        wheel_rewards.push(WheelReward::Coin(CoinReward {
+            label: "Vested Aura".to_string(),
+            coin: Coin::new(1000, "uaura"),
+            number: 1,
+            id: 1,
+            tier: None,
+            vesting: Some(VestingSchedule {
+                cliff: 200,
+                duration: 1000,
+            }),
+        }));
+
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(1, wheel_rewards))
+            .unwrap();
+
+        LOCKED_COINS
+            .save(
+                deps.as_mut().storage,
+                "uaura".to_string(),
+                &Uint128::from(1000u128),
+            )
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(100, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(40000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let spin_msg = ExecuteMsg::Spin {
+            number: Some(1),
+            referrer: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &coins(100u128, "uaura".to_string())),
+            spin_msg,
+        )
+        .unwrap();
+
+        // won coins are delivered by vesting schedule, not by claiming
+        let spins_result = player_rewards(&deps, USER);
+        assert!(spins_result[0].0);
+
+        // nothing is released before cliff
+        let env = env_with_specify(Timestamp::from_seconds(10100), 2);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            ExecuteMsg::ClaimVested {},
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+
+        // a quarter of coins is released
+        let env = env_with_specify(Timestamp::from_seconds(10250), 3);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER, &[]),
+            ExecuteMsg::ClaimVested {},
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "claim_vested")
+                .add_attribute("sender", USER)
+                .add_event(
+                    Event::new("vesting_release")
+                        .add_attribute("player", USER)
+                        .add_attribute("label", "Vested Aura")
+                        .add_attribute("kind", "coin")
+                        .add_attribute("asset", "uaura")
+                        .add_attribute("amount", "250")
+                        .add_attribute("recipient", USER)
+                )
+                .add_message(BankMsg::Send {
+                    to_address: USER.to_string(),
+                    amount: vec![Coin::new(250, "uaura")],
+                })
+        );
+
+        let vestings: Vec<VestingResponse> = from_json(
+            query(
+                deps.as_ref(),
+                env,
+                QueryMsg::GetPlayerVestings {
+                    address: USER.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            vestings,
+            vec![VestingResponse {
+                label: "Vested Aura".to_string(),
+                asset: VestingAsset::Coin(Coin::new(1000, "uaura")),
+                vested: Uint128::from(250u128),
+                released: Uint128::from(250u128),
+                claimable: Uint128::zero(),
+                cliff_end: Timestamp::from_seconds(10200),
+                end: Timestamp::from_seconds(11000),
+            }]
+        );
+
+        // the rest is released after vesting duration
+        let env = env_with_specify(Timestamp::from_seconds(12000), 4);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            ExecuteMsg::ClaimVested {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: USER.to_string(),
+                amount: vec![Coin::new(750, "uaura")],
+            })
+        );
+
+        // fully released vesting is removed
+        let vestings = VESTINGS
+            .load(deps.as_ref().storage, Addr::unchecked(USER))
+            .unwrap();
+        assert!(vestings.is_empty());
+        assert!(!LOCKED_COINS.has(deps.as_ref().storage, "uaura".to_string()));
+    }
+
+    #[test]
+    fn claim_vested_fail_with_player_not_found() {
+        let mut deps = default_setup();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            ExecuteMsg::ClaimVested {},
+        )
+        .unwrap_err();
+        match res {
+            ContractError::PlayerNotFound {} => {}
+            _ => panic!(),
+        }
+    }
+
+    /* ============================================================ Withdraw  ======================================================================== */
+    #[test]
+    fn withdraw_reward_success() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: 100,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .unwrap();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let withdraw_reward = ExecuteMsg::Withdraw {
+            recipient: Some("recipient".to_string()),
+            slot: 0,
+        };
+
+        let env = env_with_specify(Timestamp::from_seconds(21000), 1);
+
+        let res = execute(deps.as_mut(), env, mock_info(CREATOR, &[]), withdraw_reward).unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "withdraw")
+                .add_attribute("slot", "0")
+                .add_event(
+                    Event::new("withdraw")
+                        .add_attribute("admin", CREATOR)
+                        .add_attribute("slot_id", "1")
+                        .add_attribute("label", "you lose")
+                        .add_attribute("kind", "text")
+                        .add_attribute("recipient", "recipient")
+                )
+        )
+    }
+
+    #[test]
+    fn withdraw_reward_fail_with_wheel_not_activated() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: 100,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .unwrap();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let withdraw_reward = ExecuteMsg::Withdraw {
+            recipient: Some("recipient".to_string()),
+            slot: 0,
+        };
+
+        let res =
+            execute(deps.as_mut(), env, mock_info(CREATOR, &[]), withdraw_reward).unwrap_err();
+        match res {
+            ContractError::WheelNotActivated {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn withdraw_reward_fail_with_unauthorized() {
+        let mut deps = default_setup();
+
+        ADMIN_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &AdminConfig {
+                    admin: Addr::unchecked(CREATOR),
+                    // set activate to true
+                    activate: true,
+                },
+            )
+            .unwrap();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: 100,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .unwrap();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let withdraw_reward = ExecuteMsg::Withdraw {
+            recipient: Some("recipient".to_string()),
+            slot: 0,
+        };
+
+        let res = execute(deps.as_mut(), env, mock_info(USER, &[]), withdraw_reward).unwrap_err();
+        match res {
+            ContractError::Unauthorized {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn withdraw_reward_fail_with_wheel_not_end() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        // add reward
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "you lose".to_string(),
+            number: 100,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(100, wheel_rewards))
+            .unwrap();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        );
+
+        let withdraw_reward = ExecuteMsg::Withdraw {
+            recipient: Some("recipient".to_string()),
+            slot: 0,
+        };
+
+        let env = env_with_specify(Timestamp::from_seconds(19000) /* < 20000 */, 1);
+
+        let res =
+            execute(deps.as_mut(), env, mock_info(CREATOR, &[]), withdraw_reward).unwrap_err();
+        match res {
+            ContractError::WheelNotEnded {} => {}
+            _ => panic!(),
+        }
+    }
+
+    /* ============================================================ RedeemVoucher  ======================================================================== */
+    fn voucher_setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = default_setup();
+
+        // player won the code of index 3
+        save_won_reward(
+            &mut deps,
+            USER,
+            WheelReward::Voucher(VoucherReward {
+                label: "Merch coupon".to_string(),
+                code_hashes: vec![hex::encode(Sha256::digest(b"CODE-3"))],
+                next_index: 3,
+                id: 1,
+                tier: None,
+            }),
+        );
+
+        let set_oracle = ExecuteMsg::SetVoucherOracle {
+            oracle: Some("oracle".to_string()),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            set_oracle,
+        )
+        .unwrap();
+
+        deps
+    }
+
+    #[test]
+    fn redeem_voucher_success() {
+        let mut deps = voucher_setup();
+
+        let redeem_voucher = ExecuteMsg::RedeemVoucher {
+            player: USER.to_string(),
+            reward: 0,
+            code: "CODE-3".to_string(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("oracle", &[]),
+            redeem_voucher.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "redeem_voucher")
+                .add_attribute("player", USER)
+                .add_attribute("slot_id", "1")
+                .add_attribute("index", "3")
+                .add_event(
+                    Event::new("claim")
+                        .add_attribute("player", USER)
+                        .add_attribute("index", "0")
+                        .add_attribute("slot_id", "1")
+                        .add_attribute("label", "Merch coupon")
+                        .add_attribute("kind", "voucher")
+                        .add_attribute("asset", hex::encode(Sha256::digest(b"CODE-3")))
+                        .add_attribute("amount", "1")
+                        .add_attribute("recipient", USER)
+                )
+        );
+
+        let redemption: Option<VoucherRedemption> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetVoucherRedemption {
+                    slot_id: 1,
+                    index: 3,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            redemption,
+            Some(VoucherRedemption {
+                player: Addr::unchecked(USER),
+                code: "CODE-3".to_string(),
+                attested_by: Addr::unchecked("oracle"),
+                redeemed_at: mock_env().block.time,
+            })
+        );
+
+        // redeemed voucher is marked as claimed
+        let spins_result = player_rewards(&deps, USER);
+        assert!(spins_result[0].0);
+
+        // voucher can not be redeemed twice
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            redeem_voucher,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::VoucherAlreadyRedeemed {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn redeem_voucher_fail_with_invalid_code() {
+        let mut deps = voucher_setup();
+
+        let redeem_voucher = ExecuteMsg::RedeemVoucher {
+            player: USER.to_string(),
+            reward: 0,
+            code: "CODE-4".to_string(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            redeem_voucher,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InvalidCodeHash {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn redeem_voucher_fail_with_unauthorized() {
+        let mut deps = voucher_setup();
+
+        let redeem_voucher = ExecuteMsg::RedeemVoucher {
+            player: USER.to_string(),
+            reward: 0,
+            code: "CODE-3".to_string(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            redeem_voucher,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn redeem_voucher_success_after_claim_reward() {
+        let mut deps = voucher_setup();
+
+        ADMIN_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &AdminConfig {
+                    admin: Addr::unchecked(CREATOR),
+                    activate: true,
+                },
+            )
+            .unwrap();
+
+        // claiming all rewards skips the voucher
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            ExecuteMsg::ClaimReward { rewards: vec![0] },
+        )
+        .unwrap();
+        assert!(res.events.is_empty());
+        assert!(!player_rewards(&deps, USER)[0].0);
+
+        let redeem_voucher = ExecuteMsg::RedeemVoucher {
+            player: USER.to_string(),
+            reward: 0,
+            code: "CODE-3".to_string(),
+        };
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("oracle", &[]),
+            redeem_voucher,
+        )
+        .unwrap();
+        assert!(player_rewards(&deps, USER)[0].0);
+    }
+
+    /* ============================================================ CommitReveal  ======================================================================== */
+    const SEED: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+    fn commit_reveal_setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = default_setup();
+
+        // switch to commit-reveal mode
+        let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+        config.is_commit_reveal = true;
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        let commit_seed = ExecuteMsg::CommitSeed {
+            seed_hash: hex::encode(Sha256::digest([0xbbu8; 32])),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            commit_seed,
+        )
+        .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        // player spins in the round
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info(USER, &coins(2000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: Some(2),
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+        deps
+    }
+
+    #[test]
+    fn settle_spin_fail_out_of_order() {
+        let mut deps = commit_reveal_setup();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info("user2", &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(21000), 3),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::RevealSeed {
+                seed: SEED.to_string(),
+            },
+        )
+        .unwrap();
+
+        // the later job can not be settled before the earlier one
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(21000), 3),
+            mock_info("user2", &[]),
+            ExecuteMsg::SettleSpin {
+                job_id: "user2/1".to_string(),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { val } => {
+                assert_eq!(val, "the job user/0 must be settled first")
+            }
+            _ => panic!(),
+        }
+
+        for job_id in ["user/0", "user2/1"] {
+            execute(
+                deps.as_mut(),
+                env_with_specify(Timestamp::from_seconds(21000), 3),
+                mock_info("anyone", &[]),
+                ExecuteMsg::SettleSpin {
+                    job_id: job_id.to_string(),
+                },
+            )
+            .unwrap();
+        }
+        assert_eq!(player_rewards(&deps, "user2").len(), 1);
+    }
+
+    #[test]
+    fn activate_wheel_fail_with_seed_not_committed() {
+        let mut deps = default_setup();
+
+        let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+        config.is_commit_reveal = true;
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { val } => {
+                assert_eq!(val, "the seed hash must be committed before activation")
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn settle_spin_success() {
+        let mut deps = commit_reveal_setup();
+
+        // spin payment is locked until the spin is settled
+        assert_eq!(
+            LOCKED_COINS
+                .load(deps.as_ref().storage, "uaura".to_string())
+                .unwrap(),
+            Uint128::from(2000u128)
+        );
+
+        let pending_jobs: Vec<PendingJobResponse> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ListPendingJobs {
+                    start_after: None,
+                    limit: Some(10),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(pending_jobs.len(), 1);
+        assert_eq!(pending_jobs[0].job_id, "user/0");
+
+        let settle_spin = ExecuteMsg::SettleSpin {
+            job_id: "user/0".to_string(),
+        };
+
+        // seed is not revealed yet
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(16000), 3),
+            mock_info(CREATOR, &[]),
+            settle_spin.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::SeedNotRevealed {} => {}
+            _ => panic!(),
+        }
+
+        // seed can only be revealed after the wheel ends
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(16000), 3),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::RevealSeed {
+                seed: SEED.to_string(),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::WheelNotEnded {} => {}
+            _ => panic!(),
+        }
+
+        // revealed seed must match the committed hash
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(21000), 4),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::RevealSeed {
+                seed: "cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc"
+                    .to_string(),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InvalidSeed {} => {}
+            _ => panic!(),
+        }
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(21000), 4),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::RevealSeed {
+                seed: SEED.to_string(),
+            },
+        )
+        .unwrap();
+
+        // anyone can settle the spin
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(21000), 4),
+            mock_info("anyone", &[]),
+            settle_spin,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "settle_spin")
+                .add_attribute("job_id", "user/0")
+                .add_event(text_won_event(1, 0).add_attribute("job_id", "user/0"))
+                .add_event(text_won_event(2, 1).add_attribute("job_id", "user/0"))
+                .set_data(text_spin_data(Some("user/0"), &[0, 1]))
+        );
+
+        // results are linked to the settled job
+        let player_results: Vec<SpinRecord> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetPlayerResults {
+                    address: USER.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(player_results.len(), 2);
+        for result in player_results {
+            assert_eq!(result.job_id, Some("user/0".to_string()));
+            assert_eq!(result.randomness_mode, RandomnessMode::CommitReveal);
+        }
+        assert!(!LOCKED_COINS.has(deps.as_ref().storage, "uaura".to_string()));
+
+        let pending_jobs: Vec<PendingJobResponse> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ListPendingJobs {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(pending_jobs.is_empty());
+    }
+
+    #[test]
+    fn cancel_random_job_success() {
+        let mut deps = commit_reveal_setup();
+
+        let cancel_random_job = ExecuteMsg::CancelRandomJob {
+            job_id: "user/0".to_string(),
+        };
+
+        // admin still has time to reveal the seed
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(21000), 3),
+            mock_info(USER, &[]),
+            cancel_random_job.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::RandomJobNotExpired {} => {}
+            _ => panic!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(20000 + 7 * 24 * 60 * 60 + 1), 4),
+            mock_info(USER, &[]),
+            cancel_random_job,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "cancel_random_job")
+                .add_attribute("job_id", "user/0")
+                .add_event(
+                    Event::new("refund")
+                        .add_attribute("player", USER)
+                        .add_attribute("job_id", "user/0")
+                        .add_attribute("spins", "2")
+                        .add_attribute("free_spins", "0")
+                        .add_attribute("payment", "2000uaura")
+                        .add_attribute("recipient", USER)
+                )
+                .add_message(BankMsg::Send {
+                    to_address: USER.to_string(),
+                    amount: vec![Coin::new(2000, "uaura")],
+                })
+        );
+
+        // supply and spin count are restored
+        let (supply, _) = WHEEL_REWARDS.load(deps.as_ref().storage).unwrap();
+        assert_eq!(supply, 10);
+        let spinned: Option<u32> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetPlayerSpinned {
+                    address: USER.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(spinned, Some(0));
+    }
+
+    #[test]
+    fn withdraw_fail_with_pending_spins() {
+        let mut deps = commit_reveal_setup();
+
+        let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+        config.reveal_timeout = Some(100);
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+        let withdraw = ExecuteMsg::Withdraw {
+            slot: 0,
+            recipient: None,
+        };
+
+        // rewards are kept for the pending spins
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(20050), 3),
+            mock_info(CREATOR, &[]),
+            withdraw.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::PendingSpins {} => {}
+            _ => panic!(),
+        }
+
+        // admin cancels the expired job, the payment is refunded to the player
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(20101), 4),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::CancelRandomJob {
+                job_id: "user/0".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: USER.to_string(),
+                amount: vec![Coin::new(2000, "uaura")],
+            })
+        );
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(20101), 4),
+            mock_info(CREATOR, &[]),
+            withdraw,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn cancel_nois_random_job_success() {
+        let mut deps = default_setup();
+
+        // switch to advanced mode
+        let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+        config.is_advanced_randomness = true;
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info(USER, &coins(2000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: Some(2),
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+        let pending_jobs: Vec<PendingJobResponse> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetPendingJobs {
+                    address: USER.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            pending_jobs,
+            vec![PendingJobResponse {
+                job_id: "user/0".to_string(),
+                player: Addr::unchecked(USER),
+                spins: 2,
+                submitted_at: Timestamp::from_seconds(15000),
+            }]
+        );
+
+        let cancel_random_job = ExecuteMsg::CancelRandomJob {
+            job_id: "user/0".to_string(),
+        };
+
+        // randomness may still arrive
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000 + 60 * 60), 3),
+            mock_info(USER, &[]),
+            cancel_random_job.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::RandomJobNotExpired {} => {}
+            _ => panic!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000 + 60 * 60 + 1), 4),
+            mock_info(USER, &[]),
+            cancel_random_job,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "cancel_random_job")
+                .add_attribute("job_id", "user/0")
+                .add_event(
+                    Event::new("refund")
+                        .add_attribute("player", USER)
+                        .add_attribute("job_id", "user/0")
+                        .add_attribute("spins", "2")
+                        .add_attribute("free_spins", "0")
+                        .add_attribute("payment", "2000uaura")
+                        .add_attribute("recipient", USER)
+                )
+                .add_message(BankMsg::Send {
+                    to_address: USER.to_string(),
+                    amount: vec![Coin::new(2000, "uaura")],
+                })
+        );
+
+        // supply and spin count are restored
+        let (supply, _) = WHEEL_REWARDS.load(deps.as_ref().storage).unwrap();
+        assert_eq!(supply, 10);
+        assert_eq!(
+            WHITELIST
+                .load(deps.as_ref().storage, Addr::unchecked(USER))
+                .unwrap(),
+            0
+        );
+        assert!(!LOCKED_COINS.has(deps.as_ref().storage, "uaura".to_string()));
+
+        // the id of the cancelled job is not reused by the next spin
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000 + 60 * 60 + 1), 4),
+            mock_info(USER, &coins(2000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: Some(2),
+                referrer: None,
+            },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "job_id" && attr.value == "user/1"));
+    }
+
+    #[test]
+    fn mock_randomness_provider_success() {
+        let mut deps = default_setup();
+
+        // switch to advanced mode with a mock oracle
+        let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+        config.is_advanced_randomness = true;
+        config.randomness_provider = RandomnessProvider::Mock {
+            oracle: Addr::unchecked("oracle"),
+        };
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        // randomness is requested from the mock oracle
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info(USER, &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "oracle".to_string(),
+                msg: to_json_binary(&MockExecuteMsg::RequestRandomness {
+                    job_id: "user/0".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+
+        let randomness = HexBinary::from([0xaau8; 32]);
+
+        // nois proxy can not deliver randomness to a wheel using another provider
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15010), 3),
+            mock_info(NOIS_PROXY, &[]),
+            ExecuteMsg::NoisReceive {
+                callback: NoisCallback {
+                    job_id: "user/0".to_string(),
+                    published: Timestamp::from_seconds(15010),
+                    randomness: randomness.clone(),
+                },
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized {} => {}
+            _ => panic!(),
+        }
+
+        let mock_receive = ExecuteMsg::MockReceive {
+            job_id: "user/0".to_string(),
+            randomness,
+        };
+
+        // only the mock oracle can deliver randomness
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15010), 3),
+            mock_info("anyone", &[]),
+            mock_receive.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized {} => {}
+            _ => panic!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15010), 3),
+            mock_info("oracle", &[]),
+            mock_receive,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "mock_receive")
+                .add_attribute("job_id", "user/0")
+                .add_event(text_won_event(1, 0).add_attribute("job_id", "user/0"))
+                .set_data(text_spin_data(Some("user/0"), &[0]))
+        );
+
+        let spins_result = player_rewards(&deps, USER);
+        assert_eq!(spins_result.len(), 1);
+    }
+
+    /* ============================================================ CommitSpin  ======================================================================== */
+    const SECRET: &str = "player secret";
+
+    fn commit_spin_setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = default_setup();
+
+        // spins must be revealed within 10 blocks
+        let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+        config.spin_reveal_window = Some(10);
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        // player commits 2 spins at block 100
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 100),
+            mock_info(USER, &coins(2000u128, "uaura".to_string())),
+            ExecuteMsg::CommitSpin {
+                number: Some(2),
+                commitment: hex::encode(Sha256::digest(SECRET.as_bytes())),
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+        deps
+    }
+
+    #[test]
+    fn spin_fail_with_reveal_window() {
+        let mut deps = commit_spin_setup();
+
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 101),
+            mock_info(CREATOR, &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: None,
+                referrer: None,
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { val } => {
+                assert_eq!(val, "spins must be committed by CommitSpin")
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn reveal_spin_success() {
+        let mut deps = commit_spin_setup();
+
+        let spin_commit: Option<SpinCommit> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetSpinCommit {
+                    address: USER.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let spin_commit = spin_commit.unwrap();
+        assert_eq!(spin_commit.spins, 2);
+        // the seed is fixed at commit
+        assert_eq!(spin_commit.seed, HexBinary::from([0xaau8; 32]));
+
+        let reveal_spin = ExecuteMsg::RevealSpin {
+            secret: SECRET.to_string(),
+        };
+
+        // can only reveal at the end of the reveal window
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15005), 101),
+            mock_info(USER, &[]),
+            reveal_spin.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { val } => {
+                assert_eq!(val, "the spin must be revealed at block 110")
+            }
+            _ => panic!(),
+        }
+
+        // secret must match the commitment
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15050), 110),
+            mock_info(USER, &[]),
+            ExecuteMsg::RevealSpin {
+                secret: "wrong secret".to_string(),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { val } => {
+                assert_eq!(val, "the secret does not match the commitment")
+            }
+            _ => panic!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15050), 110),
+            mock_info(USER, &[]),
+            reveal_spin,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "reveal_spin")
+                .add_attribute("sender", USER)
+                .add_attribute("spins", "2")
+                .add_event(text_won_event(1, 0))
+                .add_event(text_won_event(2, 1))
+                .set_data(text_spin_data(None, &[0, 1]))
+        );
+
+        let spins_result = player_rewards(&deps, USER);
+        assert_eq!(spins_result.len(), 2);
+        assert!(!SPIN_COMMITS.has(deps.as_ref().storage, Addr::unchecked(USER)));
+    }
+
+    #[test]
+    fn expire_spin_commit_success() {
+        let mut deps = commit_spin_setup();
+
+        let expire_spin_commit = ExecuteMsg::ExpireSpinCommit {
+            player: USER.to_string(),
+        };
+
+        // still in the reveal window
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15050), 110),
+            mock_info("anyone", &[]),
+            expire_spin_commit.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::SpinCommitNotExpired {} => {}
+            _ => panic!(),
+        }
+
+        // player can not reveal after the window
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15055), 111),
+            mock_info(USER, &[]),
+            ExecuteMsg::RevealSpin {
+                secret: SECRET.to_string(),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::SpinCommitExpired {} => {}
+            _ => panic!(),
+        }
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15055), 111),
+            mock_info("anyone", &[]),
+            expire_spin_commit,
+        )
+        .unwrap();
+
+        // supply is restored but spin count is consumed
+        let (supply, _) = WHEEL_REWARDS.load(deps.as_ref().storage).unwrap();
+        assert_eq!(supply, 10);
+        let spinned: Option<u32> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetPlayerSpinned {
+                    address: USER.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(spinned, Some(2));
+        assert!(!SPIN_COMMITS.has(deps.as_ref().storage, Addr::unchecked(USER)));
+    }
+
+    /* ============================================================ SpinProof  ======================================================================== */
+    #[test]
+    fn verify_spin_success() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(2);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text 1".to_string(),
+            number: 3,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text 2".to_string(),
+            number: 5,
+            id: 2,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(8, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info(USER, &coins(2000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: Some(2),
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+        let proof: Option<SpinProof> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetSpinProof {
+                    address: USER.to_string(),
+                    index: 0,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let proof = proof.unwrap();
+        assert_eq!(
+            proof.key,
+            format!("{}{}", USER, Timestamp::from_seconds(15000))
+        );
+        assert_eq!(proof.tier_index, None);
+        assert_eq!(proof.nonce, 0);
+        // the first spin is selected from the full supply of slots
+        assert_eq!(
+            proof.weights_hash,
+            HexBinary::from(Sha256::digest(b"[[0,3,null],[1,5,null]]").as_slice())
+        );
+
+        // the second spin is selected from the supply left by the first
+        let weights = if proof.slot_index == 0 {
+            vec![(0, 2, None), (1, 5, None)]
+        } else {
+            vec![(0, 3, None), (1, 4, None)]
+        };
+
+        // weights not matching the recorded hash are rejected
+        let res: VerifySpinResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::VerifySpin {
+                    address: USER.to_string(),
+                    index: 1,
+                    weights: vec![(0, 3, None), (1, 5, None)],
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            VerifySpinResponse {
+                is_valid: false,
+                selected_slot: None,
+            }
+        );
+
+        let verify_spin = QueryMsg::VerifySpin {
+            address: USER.to_string(),
+            index: 1,
+            weights,
+        };
+
+        let res: VerifySpinResponse =
+            from_json(query(deps.as_ref(), mock_env(), verify_spin.clone()).unwrap()).unwrap();
+        let proof = SPIN_PROOFS
+            .load(deps.as_ref().storage, (Addr::unchecked(USER), 1))
+            .unwrap();
+        assert_eq!(proof.nonce, 1);
+        assert_eq!(
+            res,
+            VerifySpinResponse {
+                is_valid: true,
+                selected_slot: Some(proof.slot_index),
+            }
+        );
+
+        // the recorded slot does not match the selection
+        let mut wrong_proof = proof.clone();
+        wrong_proof.slot_index = 1 - proof.slot_index;
+        SPIN_PROOFS
+            .save(
+                deps.as_mut().storage,
+                (Addr::unchecked(USER), 1),
+                &wrong_proof,
+            )
+            .unwrap();
+
+        let res: VerifySpinResponse =
+            from_json(query(deps.as_ref(), mock_env(), verify_spin.clone()).unwrap()).unwrap();
+        assert_eq!(
+            res,
+            VerifySpinResponse {
+                is_valid: false,
+                selected_slot: Some(proof.slot_index),
+            }
+        );
+
+        // the recorded randomness is not derived from the seed
+        let mut wrong_proof = proof;
+        wrong_proof.randomness = HexBinary::from([0u8; 32]);
+        SPIN_PROOFS
+            .save(
+                deps.as_mut().storage,
+                (Addr::unchecked(USER), 1),
+                &wrong_proof,
+            )
+            .unwrap();
+
+        let res: VerifySpinResponse =
+            from_json(query(deps.as_ref(), mock_env(), verify_spin).unwrap()).unwrap();
+        assert_eq!(
+            res,
+            VerifySpinResponse {
+                is_valid: false,
+                selected_slot: None,
+            }
+        );
+    }
+
+    /* ============================================================ SpinHistory  ======================================================================== */
+    #[test]
+    fn spin_history_success() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 7,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info(USER, &coins(2000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: Some(2),
+                referrer: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(16000), 3),
+            mock_info(USER, &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(17000), 4),
+            mock_info(USER, &[]),
+            ExecuteMsg::ClaimReward { rewards: vec![0] },
+        )
+        .unwrap();
+
+        let player_results: Vec<SpinRecord> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetPlayerResults {
+                    address: USER.to_string(),
+                    start_after: None,
+                    limit: Some(1),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            player_results,
+            vec![SpinRecord {
+                spin_id: 1,
+                player: Addr::unchecked(USER),
+                index: 0,
+                reward: WheelReward::Text(TextReward {
+                    label: "text".to_string(),
+                    number: 1,
+                    id: 7,
+                    tier: None,
+                    is_losing: false,
+                }),
+                is_claimed: true,
+                height: 2,
+                time: Timestamp::from_seconds(15000),
+                price: Coin::new(1000, "uaura"),
+                randomness_mode: RandomnessMode::Basic,
+                job_id: None,
+                claimed_at: Some(Timestamp::from_seconds(17000)),
+                claim_recipient: Some(Addr::unchecked(USER)),
+            }]
+        );
+
+        // all results are won from slot 7
+        let slot_results: Vec<SpinRecord> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ListSlotResults {
+                    slot_id: 7,
+                    start_after: Some((USER.to_string(), 0)),
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(slot_results.len(), 2);
+        assert_eq!(slot_results[0].index, 1);
+
+        // only the last spin is in the time range
+        let time_results: Vec<SpinRecord> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ListResultsByTime {
+                    start: Some(Timestamp::from_seconds(15500)),
+                    end: Some(Timestamp::from_seconds(20000)),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(time_results.len(), 1);
+        assert_eq!(time_results[0].spin_id, 3);
+
+        let time_results: Vec<SpinRecord> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ListResultsByTime {
+                    start: None,
+                    end: Some(Timestamp::from_seconds(15500)),
+                    start_after: Some(1),
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(time_results.len(), 1);
+        assert_eq!(time_results[0].spin_id, 2);
+    }
+
+    #[test]
+    fn list_winners_success() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(2);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "better luck next time".to_string(),
+            number: 5,
+            id: 1,
+            tier: None,
+            is_losing: true,
+        }));
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "winner".to_string(),
+            number: 5,
+            id: 2,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info(USER, &coins(10000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: Some(10),
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+        let winners: Vec<WinnerResponse> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ListWinners {
+                    start_before: None,
+                    limit: Some(3),
+                    exclude_losing: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(winners.len(), 3);
+        for winner in &winners {
+            assert_eq!(winner.slot_id, 2);
+            assert_eq!(winner.label, "winner");
+        }
+        // most recent winners come first
+        assert!(winners[0].spin_id > winners[1].spin_id);
+
+        let winners: Vec<WinnerResponse> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ListWinners {
+                    start_before: Some(winners[2].spin_id),
+                    limit: None,
+                    exclude_losing: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(winners.len(), 2);
+
+        let results: Vec<WinnerResponse> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ListWinners {
+                    start_before: None,
+                    limit: Some(30),
+                    exclude_losing: Some(false),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(results.len(), 10);
+    }
+
+    #[test]
+    fn get_wheel_stats_success() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 7,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info(USER, &coins(2000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: Some(2),
+                referrer: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info("user2", &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(16000), 3),
+            mock_info(USER, &[]),
+            ExecuteMsg::ClaimReward { rewards: vec![0] },
+        )
+        .unwrap();
+
+        let stats: WheelStatsResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::GetWheelStats {}).unwrap())
+                .unwrap();
+        assert_eq!(
+            stats,
+            WheelStatsResponse {
+                total_spins: 3,
+                unique_players: 2,
+                revenue: coins(3000u128, "uaura".to_string()),
+                slots: vec![SlotStatsResponse {
+                    slot_id: 7,
+                    won: 3,
+                    claimed: 1,
+                    unclaimed: 2,
+                    remaining: 7,
+                }],
+                remaining_supply: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn get_wheel_stats_success_with_claimed_voucher() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Voucher(VoucherReward {
+            label: "Merch coupon".to_string(),
+            code_hashes: vec![hex::encode(Sha256::digest(b"CODE-0"))],
+            next_index: 0,
+            id: 7,
+            tier: None,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(1, wheel_rewards))
+            .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info(USER, &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+        // claiming rewards skips the voucher, then admin redeems it twice
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(16000), 3),
+            mock_info(USER, &[]),
+            ExecuteMsg::ClaimReward { rewards: vec![0] },
+        )
+        .unwrap();
+        let redeem_voucher = ExecuteMsg::RedeemVoucher {
+            player: USER.to_string(),
+            reward: 0,
+            code: "CODE-0".to_string(),
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(16000), 3),
+            mock_info(CREATOR, &[]),
+            redeem_voucher.clone(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(16000), 3),
+            mock_info(CREATOR, &[]),
+            redeem_voucher,
+        )
+        .unwrap_err();
+
+        // the claim is counted once
+        let stats: WheelStatsResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::GetWheelStats {}).unwrap())
+                .unwrap();
+        assert_eq!(
+            stats.slots,
+            vec![SlotStatsResponse {
+                slot_id: 7,
+                won: 1,
+                claimed: 1,
+                unclaimed: 0,
+                remaining: 0,
+            }]
+        );
+    }
+
+    /* ============================================================ Leaderboard  ======================================================================== */
+    #[test]
+    fn leaderboard_success() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        let set_leaderboard = ExecuteMsg::SetLeaderboard {
+            denom: "uaura".to_string(),
+            slot_values: vec![(1, Uint128::from(100u128))],
+            bonus: vec![Uint128::from(500u128), Uint128::from(200u128)],
+        };
+
+        // bonus must be funded
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &coins(500u128, "uaura".to_string())),
+            set_leaderboard.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InsufficentFund {} => {}
+            _ => panic!(),
+        }
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &coins(700u128, "uaura".to_string())),
+            set_leaderboard,
+        )
+        .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        for (player, spins) in [(USER, 3u32), ("user2", 1), ("user3", 2)] {
+            execute(
+                deps.as_mut(),
+                env_with_specify(Timestamp::from_seconds(15000), 2),
+                mock_info(
+                    player,
+                    &coins(1000u128 * spins as u128, "uaura".to_string()),
+                ),
+                ExecuteMsg::Spin {
+                    number: Some(spins),
+                    referrer: None,
+                },
+            )
+            .unwrap();
+        }
+
+        let leaderboard: Vec<PlayerScore> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetLeaderboard {
+                    order_by: LeaderboardOrder::Value,
+                    start_after: None,
+                    limit: Some(1),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            leaderboard,
+            vec![PlayerScore {
+                player: Addr::unchecked(USER),
+                value: Uint128::from(300u128),
+                spins: 3,
+            }]
+        );
+
+        let leaderboard: Vec<PlayerScore> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetLeaderboard {
+                    order_by: LeaderboardOrder::Spins,
+                    start_after: Some(USER.to_string()),
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            leaderboard
+                .iter()
+                .map(|score| score.player.as_str())
+                .collect::<Vec<_>>(),
+            vec!["user3", "user2"]
+        );
+
+        // bonus is paid after the round
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(19000), 3),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::PayLeaderboardBonus {},
+        )
+        .unwrap_err();
+        match res {
+            ContractError::WheelNotEnded {} => {}
+            _ => panic!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(21000), 4),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::PayLeaderboardBonus {},
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "pay_leaderboard_bonus")
+                .add_attribute("bonus", "700")
+                .add_event(
+                    Event::new("leaderboard_bonus")
+                        .add_attribute("player", USER)
+                        .add_attribute("rank", "1")
+                        .add_attribute("asset", "uaura")
+                        .add_attribute("amount", "500")
+                )
+                .add_event(
+                    Event::new("leaderboard_bonus")
+                        .add_attribute("player", "user3")
+                        .add_attribute("rank", "2")
+                        .add_attribute("asset", "uaura")
+                        .add_attribute("amount", "200")
+                )
+                .add_message(BankMsg::Send {
+                    to_address: USER.to_string(),
+                    amount: coins(500u128, "uaura".to_string()),
+                })
+                .add_message(BankMsg::Send {
+                    to_address: "user3".to_string(),
+                    amount: coins(200u128, "uaura".to_string()),
+                })
+        );
+        assert!(!LOCKED_COINS.has(deps.as_ref().storage, "uaura".to_string()));
+
+        // listing from an unknown player is rejected
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetLeaderboard {
+                order_by: LeaderboardOrder::Value,
+                start_after: Some("unknown".to_string()),
+                limit: None,
+            },
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn set_leaderboard_fail_with_unknown_slot() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::SetLeaderboard {
+                denom: "uaura".to_string(),
+                slot_values: vec![(1, Uint128::from(100u128)), (2, Uint128::from(100u128))],
+                bonus: vec![],
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InvalidSlotReward {} => {}
+            _ => panic!(),
+        }
+
+        // the bonus total must not overflow
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::SetLeaderboard {
+                denom: "uaura".to_string(),
+                slot_values: vec![(1, Uint128::from(100u128))],
+                bonus: vec![Uint128::MAX, Uint128::one()],
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Std(_) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn pay_leaderboard_bonus_fail_with_pending_spins() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &coins(500u128, "uaura".to_string())),
+            ExecuteMsg::SetLeaderboard {
+                denom: "uaura".to_string(),
+                slot_values: vec![(1, Uint128::from(100u128))],
+                bonus: vec![Uint128::from(500u128)],
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::ActivateWheel {
+                price: Coin::new(1000, "uaura"),
+                start_time: Some(Timestamp::from_seconds(10000)),
+                end_time: Timestamp::from_seconds(20000),
+                shuffle: None,
+            },
+        )
+        .unwrap();
+
+        SPIN_COMMITS
+            .save(
+                deps.as_mut().storage,
+                Addr::unchecked(USER),
+                &SpinCommit {
+                    commitment: hex::encode(Sha256::digest(b"secret")),
+                    spins: 1,
+                    height: 2,
+                    seed: HexBinary::from([0xaau8; 32]),
+                },
+            )
+            .unwrap();
+
+        // the ranking is not final while spins are pending
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(21000), 3),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::PayLeaderboardBonus {},
+        )
+        .unwrap_err();
+        match res {
+            ContractError::PendingSpins {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn pay_leaderboard_bonus_success_skip_zero_scores() {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &coins(700u128, "uaura".to_string())),
+            ExecuteMsg::SetLeaderboard {
+                denom: "uaura".to_string(),
+                slot_values: vec![(1, Uint128::from(100u128))],
+                bonus: vec![Uint128::from(500u128), Uint128::from(200u128)],
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::ActivateWheel {
+                price: Coin::new(1000, "uaura"),
+                start_time: Some(Timestamp::from_seconds(10000)),
+                end_time: Timestamp::from_seconds(20000),
+                shuffle: None,
+            },
+        )
+        .unwrap();
+
+        for (player, value) in [(USER, 300u128), ("user2", 0)] {
+            player_scores()
+                .save(
+                    deps.as_mut().storage,
+                    Addr::unchecked(player),
+                    &PlayerScore {
+                        player: Addr::unchecked(player),
+                        value: Uint128::from(value),
+                        spins: 3,
+                    },
+                )
+                .unwrap();
+        }
+
+        // the player without any score gets no bonus
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(21000), 2),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::PayLeaderboardBonus {},
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "pay_leaderboard_bonus")
+                .add_attribute("bonus", "500")
+                .add_event(
+                    Event::new("leaderboard_bonus")
+                        .add_attribute("player", USER)
+                        .add_attribute("rank", "1")
+                        .add_attribute("asset", "uaura")
+                        .add_attribute("amount", "500")
+                )
+                .add_message(BankMsg::Send {
+                    to_address: USER.to_string(),
+                    amount: coins(500u128, "uaura".to_string()),
+                })
+        );
+        assert!(!LOCKED_COINS.has(deps.as_ref().storage, "uaura".to_string()));
+    }
+
+    /* ============================================================ Referral  ======================================================================== */
+
+    fn referral_setup(reward: ReferralReward) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = default_setup();
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::SetReferralReward {
+                reward: Some(reward),
+            },
+        )
+        .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        deps
+    }
+
+    #[test]
+    fn referral_fee_share_success() {
+        let mut deps = referral_setup(ReferralReward::FeeShare {
+            rate: Decimal::percent(10),
+        });
+        let env = env_with_specify(Timestamp::from_seconds(15000), 2);
+
+        // player can not refer themselves
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user2", &coins(2000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: Some(2),
+                referrer: Some("user2".to_string()),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InvalidReferrer {} => {}
+            _ => panic!(),
+        }
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user2", &coins(2000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: Some(2),
+                referrer: Some(USER.to_string()),
+            },
+        )
+        .unwrap();
+
+        // referral is stored only once
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user2", &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: None,
+                referrer: Some("user3".to_string()),
+            },
+        )
+        .unwrap();
+
+        let referral: Option<Referral> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetReferral {
+                    address: "user2".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            referral,
+            Some(Referral {
+                referrer: Addr::unchecked(USER),
+                is_rewarded: true,
+            })
+        );
+
+        // referral cycles are rejected
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: None,
+                referrer: Some("user2".to_string()),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InvalidReferrer {} => {}
+            _ => panic!(),
+        }
+
+        // referrer earns a share of the first paid spins only
+        let rewards: ReferralRewardsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetReferralRewards {
+                    address: USER.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            rewards,
+            ReferralRewardsResponse {
+                earnings: coins(200u128, "uaura".to_string()),
+                free_spins: 0,
+            }
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            ExecuteMsg::ClaimReferralEarnings {},
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "claim_referral_earnings")
+                .add_attribute("sender", USER)
+                .add_event(
+                    Event::new("referral_claim")
+                        .add_attribute("referrer", USER)
+                        .add_attribute("asset", "uaura")
+                        .add_attribute("amount", "200")
+                )
+                .add_message(BankMsg::Send {
+                    to_address: USER.to_string(),
+                    amount: coins(200u128, "uaura".to_string()),
+                })
+        );
+        assert!(!LOCKED_COINS.has(deps.as_ref().storage, "uaura".to_string()));
+    }
+
+    #[test]
+    fn referral_free_spins_success() {
+        let mut deps = referral_setup(ReferralReward::FreeSpins { spins: 2 });
+        let env = env_with_specify(Timestamp::from_seconds(15000), 2);
+
+        // free spins are capped by the paid spins of referred player
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user2", &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: None,
+                referrer: Some(USER.to_string()),
+            },
+        )
+        .unwrap();
+
+        let rewards: ReferralRewardsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetReferralRewards {
+                    address: USER.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(rewards.free_spins, 1);
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user3", &coins(3000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: Some(3),
+                referrer: Some(USER.to_string()),
+            },
+        )
+        .unwrap();
+
+        let rewards: ReferralRewardsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetReferralRewards {
+                    address: USER.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(rewards.free_spins, 3);
+
+        // free spins are used before paid spins
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::Spin {
+                number: Some(4),
+                referrer: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(player_rewards(&deps, USER).len(), 4);
+
+        let rewards: ReferralRewardsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetReferralRewards {
+                    address: USER.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(rewards.free_spins, 0);
+    }
+
+    #[test]
+    fn set_referral_reward_fail_with_invalid_reward() {
+        let mut deps = default_setup();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::SetReferralReward {
+                reward: Some(ReferralReward::FreeSpins { spins: 0 }),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { val } => {
+                assert_eq!(val, "free spins reward must be greater than 0")
+            }
+            _ => panic!(),
+        }
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &coins(10000u128, "uaura".to_string())),
+            ExecuteMsg::SetJackpot {
+                label: "jackpot".to_string(),
+                seed: Coin::new(10000, "uaura"),
+                contribution_rate: Decimal::percent(60),
+                probability: Decimal::percent(1),
+            },
+        )
+        .unwrap();
+
+        // fee share and jackpot contribution must not exceed the spin fee
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::SetReferralReward {
+                reward: Some(ReferralReward::FeeShare {
+                    rate: Decimal::percent(50),
+                }),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { val } => assert_eq!(
+                val,
+                "the sum of fee share rate and contribution rate must not be greater than 1"
+            ),
+            _ => panic!(),
+        }
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::SetReferralReward {
+                reward: Some(ReferralReward::FeeShare {
+                    rate: Decimal::percent(40),
+                }),
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn referral_success_with_commit_spin_and_spin_for() {
+        let mut deps = commit_spin_setup();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 100),
+            mock_info("user2", &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::CommitSpin {
+                number: None,
+                commitment: hex::encode(Sha256::digest(SECRET.as_bytes())),
+                referrer: Some(USER.to_string()),
+            },
+        )
+        .unwrap();
+
+        // the commit payment is final so the referrer is rewarded at commit
+        let referral: Option<Referral> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetReferral {
+                    address: "user2".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            referral,
+            Some(Referral {
+                referrer: Addr::unchecked(USER),
+                is_rewarded: true,
+            })
+        );
+
+        let mut deps = spin_for_setup(false);
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info("operator", &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::SpinFor {
+                player: "user2".to_string(),
+                number: None,
+                callback: None,
+                referrer: Some(USER.to_string()),
+            },
+        )
+        .unwrap();
+
+        let referral: Option<Referral> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetReferral {
+                    address: "user2".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            referral,
+            Some(Referral {
+                referrer: Addr::unchecked(USER),
+                is_rewarded: true,
+            })
+        );
+    }
+
+    /* ============================================================ SpinFor  ======================================================================== */
+
+    fn spin_for_setup(
+        is_advanced_randomness: bool,
+    ) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = default_setup();
+
+        if is_advanced_randomness {
+            let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+            config.is_advanced_randomness = true;
+            config.randomness_provider = RandomnessProvider::Mock {
+                oracle: Addr::unchecked("oracle"),
+            };
+            CONFIG.save(deps.as_mut().storage, &config).unwrap();
+        }
+
+        let mut wheel_rewards: Vec<WheelReward> = Vec::with_capacity(1);
+        wheel_rewards.push(WheelReward::Text(TextReward {
+            label: "text".to_string(),
+            number: 10,
+            id: 1,
+            tier: None,
+            is_losing: false,
+        }));
+        WHEEL_REWARDS
+            .save(deps.as_mut().storage, &(10, wheel_rewards))
+            .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::AddOperators {
+                addresses: vec!["operator".to_string()],
+            },
+        )
+        .unwrap();
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        deps
+    }
+
+    #[test]
+    fn spin_for_success() {
+        let mut deps = spin_for_setup(false);
+
+        let spin_for = ExecuteMsg::SpinFor {
+            player: USER.to_string(),
+            number: None,
+            callback: Some(Binary::from(b"quest-1")),
+            referrer: None,
+        };
+
+        // only registered operators can spin for players
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info("anyone", &coins(1000u128, "uaura".to_string())),
+            spin_for.clone(),
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized {} => {}
+            _ => panic!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info("operator", &coins(1000u128, "uaura".to_string())),
+            spin_for,
+        )
+        .unwrap();
+
+        // results are sent back to the operator immediately in basic mode
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "operator".to_string(),
+                msg: to_json_binary(&OperatorExecuteMsg::SpinCallback {
+                    result: from_json(text_spin_data(None, &[0])).unwrap(),
+                    data: Some(Binary::from(b"quest-1")),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(res.data, Some(text_spin_data(None, &[0])));
+        assert_eq!(player_rewards(&deps, USER).len(), 1);
+    }
+
+    #[test]
+    fn spin_for_advanced_randomness_success() {
+        let mut deps = spin_for_setup(true);
+
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info("operator", &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::SpinFor {
+                player: USER.to_string(),
+                number: None,
+                callback: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+        // only the randomness is requested
+        assert_eq!(res.messages.len(), 1);
+
+        // results are sent back to the operator when the randomness arrives
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15010), 3),
+            mock_info("oracle", &[]),
+            ExecuteMsg::MockReceive {
+                job_id: "user/0".to_string(),
+                randomness: HexBinary::from([0xaau8; 32]),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "operator".to_string(),
+                msg: to_json_binary(&OperatorExecuteMsg::SpinCallback {
+                    result: from_json(text_spin_data(Some("user/0"), &[0])).unwrap(),
+                    data: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        // the operator can not revert the spin by failing the callback
+        assert_eq!(res.messages[0].reply_on, ReplyOn::Error);
+        assert!(res.messages[0].gas_limit.is_some());
+
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: res.messages[0].id,
+                result: SubMsgResult::Err("callback failed".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "spin_callback_failed")
+                .add_attribute("error", "callback failed")
+        );
+        assert_eq!(player_rewards(&deps, USER).len(), 1);
+    }
+
+    #[test]
+    fn cancel_random_job_success_refund_operator() {
+        let mut deps = spin_for_setup(true);
+
+        FREE_SPINS
+            .save(deps.as_mut().storage, Addr::unchecked(USER), &1)
+            .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info("operator", &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::SpinFor {
+                player: USER.to_string(),
+                number: None,
+                callback: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+        // spins paid by the operator do not use free spins of player
+        assert_eq!(
+            FREE_SPINS
+                .load(deps.as_ref().storage, Addr::unchecked(USER))
+                .unwrap(),
+            1
+        );
+
+        // the operator is refunded when the job expires
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(18601), 3),
+            mock_info("operator", &[]),
+            ExecuteMsg::CancelRandomJob {
+                job_id: "user/0".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "operator".to_string(),
+                amount: coins(1000u128, "uaura".to_string()),
+            })
+        );
+        assert_eq!(
+            FREE_SPINS
+                .load(deps.as_ref().storage, Addr::unchecked(USER))
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn cancel_random_job_success_after_remove_whitelist() {
+        let mut deps = spin_for_setup(true);
+
+        for _ in 0..2 {
+            execute(
+                deps.as_mut(),
+                env_with_specify(Timestamp::from_seconds(15000), 2),
+                mock_info("operator", &coins(1000u128, "uaura".to_string())),
+                ExecuteMsg::SpinFor {
+                    player: USER.to_string(),
+                    number: None,
+                    callback: None,
+                    referrer: None,
+                },
+            )
+            .unwrap();
+        }
+
+        // the player is removed from the whitelist after spinning
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::RemoveWhitelist {
+                addresses: vec![USER.to_string()],
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(18601), 3),
+            mock_info("operator", &[]),
+            ExecuteMsg::CancelRandomJob {
+                job_id: "user/0".to_string(),
+            },
+        )
+        .unwrap();
+
+        // then re-added without any spin
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::AddWhitelist {
+                addresses: vec![USER.to_string()],
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(18601), 3),
+            mock_info("operator", &[]),
+            ExecuteMsg::CancelRandomJob {
+                job_id: "user/1".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            WHITELIST
+                .load(deps.as_ref().storage, Addr::unchecked(USER))
+                .unwrap(),
+            0
+        );
+    }
+
+    /* ============================================================ UpdateConfig  ======================================================================== */
+    #[test]
+    fn update_config_success() {
+        let mut deps = default_setup();
+
+        // only admin can update config
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            ExecuteMsg::UpdateConfig {
+                config: ConfigUpdate {
+                    is_public: Some(false),
+                    ..ConfigUpdate::default()
+                },
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Unauthorized {} => {}
+            _ => panic!(),
+        }
+
+        // all fields can be updated before activation
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::UpdateConfig {
+                config: ConfigUpdate {
+                    wheel_name: Some("new name".to_string()),
+                    is_public: Some(false),
+                    ..ConfigUpdate::default()
+                },
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "update_config")
+                .add_event(
+                    Event::new("config_update")
+                        .add_attribute("field", "wheel_name")
+                        .add_attribute("old", "test")
+                        .add_attribute("new", "new name")
+                )
+                .add_event(
+                    Event::new("config_update")
+                        .add_attribute("field", "is_public")
+                        .add_attribute("old", "true")
+                        .add_attribute("new", "false")
+                )
+        );
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin::new(1000, "uaura"),
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(9000), 1),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 2);
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::UpdateConfig {
+                config: ConfigUpdate {
+                    is_public: Some(true),
+                    ..ConfigUpdate::default()
+                },
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { val } => {
+                assert_eq!(val, "is_public can not be updated after activation")
+            }
+            _ => panic!(),
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::UpdateConfig {
+                config: ConfigUpdate {
+                    max_spins_per_address: Some(50),
+                    ..ConfigUpdate::default()
+                },
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { val } => {
+                assert_eq!(val, "the maximum number of spins can only be raised")
+            }
+            _ => panic!(),
+        }
+
+        // a safe subset can be updated after activation
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::UpdateConfig {
+                config: ConfigUpdate {
+                    max_spins_per_address: Some(200),
+                    nois_proxy: Some("new_nois_proxy".to_string()),
+                    end_time: Some(Timestamp::from_seconds(30000)),
+                    ..ConfigUpdate::default()
+                },
+            },
+        )
+        .unwrap();
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.wheel_name, "new name");
+        assert!(!config.is_public);
+        assert_eq!(config.max_spins_per_address, 200);
+        assert_eq!(config.nois_proxy, Some(Addr::unchecked("new_nois_proxy")));
+        assert_eq!(config.end_time, Some(Timestamp::from_seconds(30000)));
+
+        // an ended round can not be extended
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(31000), 3),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::UpdateConfig {
+                config: ConfigUpdate {
+                    end_time: Some(Timestamp::from_seconds(40000)),
+                    ..ConfigUpdate::default()
+                },
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::WheelEnded {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn update_config_success_clear_optional_fields() {
+        let mut deps = default_setup();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::UpdateConfig {
+                config: ConfigUpdate {
+                    pity_threshold: Some(FieldUpdate::Set { value: 3 }),
+                    spin_reveal_window: Some(FieldUpdate::Set { value: 10 }),
+                    ..ConfigUpdate::default()
+                },
+            },
+        )
+        .unwrap();
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.pity_threshold, Some(3));
+        assert_eq!(config.spin_reveal_window, Some(10));
+
+        // optional fields can be cleared before activation
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::UpdateConfig {
+                config: ConfigUpdate {
+                    pity_threshold: Some(FieldUpdate::Clear {}),
+                    spin_reveal_window: Some(FieldUpdate::Clear {}),
+                    ..ConfigUpdate::default()
+                },
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("action", "update_config")
+                .add_event(
+                    Event::new("config_update")
+                        .add_attribute("field", "pity_threshold")
+                        .add_attribute("old", "3")
+                        .add_attribute("new", "null")
+                )
+                .add_event(
+                    Event::new("config_update")
+                        .add_attribute("field", "spin_reveal_window")
+                        .add_attribute("old", "10")
+                        .add_attribute("new", "null")
+                )
+        );
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.pity_threshold, None);
+        assert_eq!(config.spin_reveal_window, None);
+    }
+
+    #[test]
+    fn update_config_fail_change_nois_proxy_with_pending_jobs() {
+        let mut deps = spin_for_setup(true);
+
+        execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 2),
+            mock_info("operator", &coins(1000u128, "uaura".to_string())),
+            ExecuteMsg::SpinFor {
+                player: USER.to_string(),
+                number: None,
+                callback: None,
+                referrer: None,
+            },
+        )
+        .unwrap();
+
+        // randomness of the pending job is sent by the current proxy
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(15000), 3),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::UpdateConfig {
+                config: ConfigUpdate {
+                    nois_proxy: Some("new_nois_proxy".to_string()),
+                    ..ConfigUpdate::default()
+                },
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::PendingSpins {} => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn jackpot_funds_locked_until_round_end() {
+        let mut deps = default_setup();
+
+        let env = env_with_specify(Timestamp::from_seconds(15000), 1);
+
+        let set_jackpot = ExecuteMsg::SetJackpot {
+            label: "jackpot".to_string(),
+            seed: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(10000u128),
+            },
+            contribution_rate: Decimal::percent(10),
+            probability: Decimal::percent(1),
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &coins(10000u128, "uaura".to_string())),
+            set_jackpot,
+        )
+        .unwrap();
+
+        // coins of other denoms are rejected
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(
+                CREATOR,
+                &[Coin::new(5000u128, "uaura"), Coin::new(5000u128, "uatom")],
+            ),
+            ExecuteMsg::FundJackpot {},
+        )
+        .unwrap_err();
+        match res {
+            ContractError::CustomError { .. } => {}
+            _ => panic!(),
+        }
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &coins(5000u128, "uaura".to_string())),
+            ExecuteMsg::FundJackpot {},
+        )
+        .unwrap();
+
+        // the reserve can not overflow
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &coins(u128::MAX, "uaura".to_string())),
+            ExecuteMsg::FundJackpot {},
+        )
+        .unwrap_err();
+        match res {
+            ContractError::Std(StdError::Overflow { .. }) => {}
+            _ => panic!(),
+        }
+
+        // the jackpot slot id is reserved
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::AddReward {
+                reward: WheelReward::Text(TextReward {
+                    label: "text".to_string(),
+                    number: 1,
+                    id: u32::MAX,
+                    tier: None,
+                    is_losing: false,
+                }),
+            },
+        )
+        .unwrap_err();
+        match res {
+            ContractError::InvalidSlotReward {} => {}
+            _ => panic!(),
+        }
+
+        // the seed and the reserve are locked
+        assert_eq!(
+            LOCKED_COINS
+                .load(deps.as_ref().storage, "uaura".to_string())
+                .unwrap(),
+            Uint128::from(15000u128)
+        );
+
+        let activate_wheel = ExecuteMsg::ActivateWheel {
+            price: Coin {
+                denom: "uaura".to_string(),
+                amount: Uint128::from(1000u128),
+            },
+            start_time: Some(Timestamp::from_seconds(10000)),
+            end_time: Timestamp::from_seconds(20000),
+            shuffle: None,
+        };
+
+        _ = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR, &[]),
+            activate_wheel,
+        )
+        .unwrap();
+
+        // the jackpot can not be won anymore after the round, so it is withdrawable
+        deps.querier
+            .update_balance(env.contract.address.clone(), coins(15000u128, "uaura"));
+
+        let res = execute(
+            deps.as_mut(),
+            env_with_specify(Timestamp::from_seconds(21000), 1),
+            mock_info(CREATOR, &[]),
+            ExecuteMsg::WithdrawCoin {
+                denom: "uaura".to_string(),
+                recipient: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: CREATOR.to_string(),
+                amount: coins(15000u128, "uaura"),
+            })
+        );
+        assert!(LOCKED_COINS
+            .may_load(deps.as_ref().storage, "uaura".to_string())
+            .unwrap()
+            .is_none());
+    }
 }
@@ -39,6 +39,24 @@ pub enum ContractError {
     #[error("Invalid randomness")]
     InvalidRandomness {},
 
+    #[error("Invalid seed")]
+    InvalidSeed {},
+
+    #[error("Seed not revealed")]
+    SeedNotRevealed {},
+
+    #[error("Spin commit not found")]
+    SpinCommitNotFound {},
+
+    #[error("Spin commit expired")]
+    SpinCommitExpired {},
+
+    #[error("Spin commit not expired")]
+    SpinCommitNotExpired {},
+
+    #[error("Random job not expired")]
+    RandomJobNotExpired {},
+
     #[error("Player not found")]
     PlayerNotFound {},
 
@@ -63,6 +81,39 @@ pub enum ContractError {
     #[error("Random job not found")]
     RandomJobNotFound {},
 
+    #[error("Invalid vesting schedule")]
+    InvalidVestingSchedule {},
+
+    #[error("Call target not allowed")]
+    CallTargetNotAllowed {},
+
+    #[error("Invalid code hash")]
+    InvalidCodeHash {},
+
+    #[error("Voucher already redeemed")]
+    VoucherAlreadyRedeemed {},
+
+    #[error("Wheel is not minter")]
+    NotMinter {},
+
+    #[error("Invalid amount range")]
+    InvalidAmountRange {},
+
+    #[error("Invalid bundle")]
+    InvalidBundle {},
+
+    #[error("Invalid tier")]
+    InvalidTier {},
+
+    #[error("Jackpot not found")]
+    JackpotNotFound {},
+
+    #[error("Invalid referrer")]
+    InvalidReferrer {},
+
+    #[error("Spins are pending")]
+    PendingSpins {},
+
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
     // Add any other custom errors you like here.
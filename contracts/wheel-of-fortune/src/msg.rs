@@ -1,6 +1,9 @@
-use crate::state::{Config, WheelReward};
+use crate::state::{
+    Config, Jackpot, Leaderboard, PlayerScore, Referral, ReferralReward, SeedCommitment,
+    SpinCommit, SpinProof, SpinRecord, Tier, VestingAsset, VoucherRedemption, WheelReward,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin, Timestamp};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, HexBinary, Timestamp, Uint128};
 use nois::NoisCallback;
 
 /// Message type for `instantiate` entry_point
@@ -14,8 +17,73 @@ pub struct InstantiateMsg {
     pub max_spins_per_address: u32,
     pub is_public: bool,
     pub is_advanced_randomness: bool,
-    // bench32 string address
-    pub nois_proxy: String,
+    // can not be enabled together with advanced randomness
+    #[serde(default)]
+    pub is_commit_reveal: bool,
+    // bench32 string address, required by the nois randomness provider in advanced mode
+    #[serde(default)]
+    pub nois_proxy: Option<String>,
+    // default is nois
+    #[serde(default)]
+    pub randomness_provider: RandomnessProviderMsg,
+    // if set, must greater than 0
+    pub pity_threshold: Option<u32>,
+    // if set, must greater than 0
+    #[serde(default)]
+    pub spin_reveal_window: Option<u64>,
+    // if set, must greater than 0, default is 7 days
+    #[serde(default)]
+    pub reveal_timeout: Option<u64>,
+}
+
+/// Source of randomness in advanced mode, oracle addresses are validated when saved
+#[cw_serde]
+pub enum RandomnessProviderMsg {
+    Nois {},
+    Vrf { oracle: String },
+    Mock { oracle: String },
+}
+
+impl Default for RandomnessProviderMsg {
+    fn default() -> Self {
+        RandomnessProviderMsg::Nois {}
+    }
+}
+
+/// Change of an optional config field
+#[cw_serde]
+pub enum FieldUpdate<T> {
+    Set { value: T },
+    Clear {},
+}
+
+impl<T> FieldUpdate<T> {
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Set { value } => Some(value),
+            Self::Clear {} => None,
+        }
+    }
+}
+
+/// Changes of wheel config, unset fields are not changed
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub wheel_name: Option<String>,
+    // can only be raised after activation
+    pub max_spins_per_address: Option<u32>,
+    pub is_public: Option<bool>,
+    pub is_advanced_randomness: Option<bool>,
+    pub is_commit_reveal: Option<bool>,
+    pub nois_proxy: Option<String>,
+    pub randomness_provider: Option<RandomnessProviderMsg>,
+    pub pity_threshold: Option<FieldUpdate<u32>>,
+    pub spin_reveal_window: Option<FieldUpdate<u64>>,
+    // cleared to use the default timeout
+    pub reveal_timeout: Option<FieldUpdate<u64>>,
+    // can only be extended after activation
+    pub end_time: Option<Timestamp>,
 }
 
 /// Message type for `execute` entry_point
@@ -30,6 +98,22 @@ pub enum ExecuteMsg {
         addresses: Vec<String>,
     },
 
+    AddCallTargets {
+        addresses: Vec<String>,
+    },
+
+    RemoveCallTargets {
+        addresses: Vec<String>,
+    },
+
+    AddOperators {
+        addresses: Vec<String>,
+    },
+
+    RemoveOperators {
+        addresses: Vec<String>,
+    },
+
     RemoveReward {
         slot: u32,
     },
@@ -38,6 +122,49 @@ pub enum ExecuteMsg {
         reward: WheelReward,
     },
 
+    SetJackpot {
+        label: String,
+        seed: Coin,
+        contribution_rate: Decimal,
+        probability: Decimal,
+    },
+
+    FundJackpot {},
+
+    SetTiers {
+        tiers: Vec<Tier>,
+    },
+
+    CommitSeed {
+        seed_hash: String,
+    },
+
+    RevealSeed {
+        seed: String,
+    },
+
+    SetVoucherOracle {
+        oracle: Option<String>,
+    },
+
+    SetLeaderboard {
+        denom: String,
+        // (slot id, prize value)
+        slot_values: Vec<(u32, Uint128)>,
+        // bonus for the top players by prize value, by rank
+        bonus: Vec<Uint128>,
+    },
+
+    PayLeaderboardBonus {},
+
+    SetReferralReward {
+        reward: Option<ReferralReward>,
+    },
+
+    UpdateConfig {
+        config: ConfigUpdate,
+    },
+
     ActivateWheel {
         price: Coin,
         start_time: Option<Timestamp>,
@@ -55,19 +182,105 @@ pub enum ExecuteMsg {
         recipient: Option<String>,
     },
 
+    // admin or voucher oracle
+    RedeemVoucher {
+        player: String,
+        reward: u32,
+        code: String,
+    },
+
     // user methods
+    // rejected in basic mode when the wheel has a spin reveal window, `CommitSpin` is used instead
     Spin {
         number: Option<u32>,
+        // address of the player who referred sender, only counted on sender's first spin
+        referrer: Option<String>,
+    },
+
+    CommitSpin {
+        number: Option<u32>,
+        commitment: String,
+        // address of the player who referred sender, only counted on sender's first spin
+        referrer: Option<String>,
+    },
+
+    RevealSpin {
+        secret: String,
     },
 
     ClaimReward {
         rewards: Vec<u32>,
     },
 
+    ClaimVested {},
+
+    ClaimReferralEarnings {},
+
+    CancelRandomJob {
+        job_id: String,
+    },
+
+    // operator methods
+    SpinFor {
+        player: String,
+        number: Option<u32>,
+        // passed back to the operator with the results
+        callback: Option<Binary>,
+        // address of the player who referred player, only counted on player's first spin
+        referrer: Option<String>,
+    },
+
+    // anyone
+    SettleSpin {
+        job_id: String,
+    },
+
+    ExpireSpinCommit {
+        player: String,
+    },
+
     // nois callback
     NoisReceive {
         callback: NoisCallback,
     },
+
+    // vrf oracle callback
+    VrfReceive {
+        callback: VrfCallback,
+    },
+
+    // mock oracle callback
+    MockReceive {
+        job_id: String,
+        randomness: HexBinary,
+    },
+}
+
+#[cw_serde]
+pub struct VrfCallback {
+    pub request_id: String,
+    pub randomness: HexBinary,
+}
+
+/// Message requesting randomness from a VRF oracle
+#[cw_serde]
+pub enum VrfExecuteMsg {
+    RequestRandomness { request_id: String },
+}
+
+/// Message requesting randomness from a mock oracle
+#[cw_serde]
+pub enum MockExecuteMsg {
+    RequestRandomness { job_id: String },
+}
+
+/// Message sent to the operator with results of spins made by `SpinFor`
+#[cw_serde]
+pub enum OperatorExecuteMsg {
+    SpinCallback {
+        result: SpinResponse,
+        data: Option<Binary>,
+    },
 }
 
 /// Message type for `query` entry_point
@@ -91,12 +304,201 @@ pub enum QueryMsg {
 
     #[returns(Option<Vec<WhiteListResponse>>)]
     GetWhiteList {},
+
+    #[returns(u32)]
+    GetPlayerLosingStreak { address: String },
+
+    #[returns(Option<Jackpot>)]
+    GetJackpot {},
+
+    #[returns(Vec<TierResponse>)]
+    GetTiers {},
+
+    #[returns(Vec<SpinRecord>)]
+    GetPlayerResults {
+        address: String,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<SpinRecord>)]
+    ListSlotResults {
+        slot_id: u32,
+        // (player, index) of the last record
+        start_after: Option<(String, u32)>,
+        limit: Option<u32>,
+    },
+
+    #[returns(WheelStatsResponse)]
+    GetWheelStats {},
+
+    #[returns(Option<Leaderboard>)]
+    GetLeaderboardConfig {},
+
+    #[returns(Vec<PlayerScore>)]
+    GetLeaderboard {
+        order_by: LeaderboardOrder,
+        // address of the last player
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Option<ReferralReward>)]
+    GetReferralReward {},
+
+    #[returns(Option<Referral>)]
+    GetReferral { address: String },
+
+    #[returns(ReferralRewardsResponse)]
+    GetReferralRewards { address: String },
+
+    #[returns(Vec<WinnerResponse>)]
+    ListWinners {
+        // spin id of the last winner, winners are listed from the most recent
+        start_before: Option<u64>,
+        limit: Option<u32>,
+        // exclude results of losing text slots, default is true
+        exclude_losing: Option<bool>,
+    },
+
+    #[returns(Vec<SpinRecord>)]
+    ListResultsByTime {
+        start: Option<Timestamp>,
+        end: Option<Timestamp>,
+        // spin id of the last record
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<PendingJobResponse>)]
+    GetPendingJobs { address: String },
+
+    #[returns(Option<SpinProof>)]
+    GetSpinProof { address: String, index: u32 },
+
+    // `weights` is the weighted list of (slot index, weight, tier index) the spin is selected from
+    #[returns(VerifySpinResponse)]
+    VerifySpin {
+        address: String,
+        index: u32,
+        weights: Vec<(u32, u32, Option<u32>)>,
+    },
+
+    #[returns(Vec<PendingJobResponse>)]
+    ListPendingJobs {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Option<SpinCommit>)]
+    GetSpinCommit { address: String },
+
+    #[returns(Option<SeedCommitment>)]
+    GetSeedCommitment {},
+
+    #[returns(Vec<VestingResponse>)]
+    GetPlayerVestings { address: String },
+
+    #[returns(Vec<Addr>)]
+    GetCallTargets {},
+
+    #[returns(Vec<Addr>)]
+    GetOperators {},
+
+    #[returns(Option<VoucherRedemption>)]
+    GetVoucherRedemption { slot_id: u32, index: u32 },
 }
 
 #[cw_serde]
 pub struct WhiteListResponse {
     pub addresses: Vec<Addr>,
 }
+
+#[cw_serde]
+pub struct TierResponse {
+    pub name: String,
+    pub weight: u32,
+    // remaining supply of all slots in tier
+    pub supply: u32,
+    // chance of picking the tier on next spin
+    pub odds: Decimal,
+}
+#[cw_serde]
+pub struct VestingResponse {
+    pub label: String,
+    pub asset: VestingAsset,
+    // amount unlocked so far, including released amount
+    pub vested: Uint128,
+    pub released: Uint128,
+    // amount can be released by `ClaimVested` now
+    pub claimable: Uint128,
+    pub cliff_end: Timestamp,
+    pub end: Timestamp,
+}
 // We define a custom struct for each query response
 // #[cw_serde]
 // pub struct YourQueryResponse {}
+
+#[cw_serde]
+pub struct PendingJobResponse {
+    pub job_id: String,
+    pub player: Addr,
+    pub spins: u32,
+    pub submitted_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct VerifySpinResponse {
+    pub is_valid: bool,
+    // slot selected again from the given weighted list, if it matches the recorded hash and the randomness is derived from the recorded seed
+    pub selected_slot: Option<u32>,
+}
+
+#[cw_serde]
+pub struct WinnerResponse {
+    pub spin_id: u64,
+    pub player: Addr,
+    pub slot_id: u32,
+    pub label: String,
+    pub time: Timestamp,
+}
+
+#[cw_serde]
+pub struct SlotStatsResponse {
+    pub slot_id: u32,
+    pub won: u32,
+    pub claimed: u32,
+    pub unclaimed: u32,
+    pub remaining: u32,
+}
+
+#[cw_serde]
+pub struct WheelStatsResponse {
+    pub total_spins: u64,
+    pub unique_players: u64,
+    pub revenue: Vec<Coin>,
+    pub slots: Vec<SlotStatsResponse>,
+    pub remaining_supply: u32,
+}
+
+#[cw_serde]
+pub enum LeaderboardOrder {
+    Value,
+    Spins,
+}
+
+#[cw_serde]
+pub struct ReferralRewardsResponse {
+    // fee shares not claimed yet
+    pub earnings: Vec<Coin>,
+    pub free_spins: u32,
+}
+
+#[cw_serde]
+pub struct SpinResponse {
+    pub player: Addr,
+    // job of pending spins, rewards are selected when it is fulfilled
+    pub job_id: Option<String>,
+    // (reward index of player, won reward)
+    pub rewards: Vec<(u32, WheelReward)>,
+}
@@ -1,6 +1,8 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{
+    Addr, Binary, Coin, Decimal, HexBinary, StdError, StdResult, Timestamp, Uint128,
+};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
 
 #[cw_serde]
 pub struct Config {
@@ -8,13 +10,43 @@ pub struct Config {
     pub max_spins_per_address: u32,
     pub is_public: bool,
     pub is_advanced_randomness: bool,
+    // spins are settled with a seed committed by admin and revealed after the round
+    #[serde(default)]
+    pub is_commit_reveal: bool,
     pub start_time: Option<Timestamp>,
     pub end_time: Option<Timestamp>,
-    pub nois_proxy: Addr,
+    pub nois_proxy: Option<Addr>,
+    // source of randomness in advanced mode
+    #[serde(default)]
+    pub randomness_provider: RandomnessProvider,
     pub price: Coin,
+    // number of consecutive losing spins before a win is guaranteed
+    pub pity_threshold: Option<u32>,
+    // if set, basic mode spins are committed and revealed this number of blocks later
+    #[serde(default)]
+    pub spin_reveal_window: Option<u64>,
+    // seconds after the wheel ends before pending commit-reveal spins can be refunded
+    #[serde(default)]
+    pub reveal_timeout: Option<u64>,
 }
 pub const CONFIG: Item<Config> = Item::new("config");
 
+#[cw_serde]
+pub enum RandomnessProvider {
+    // nois proxy at `nois_proxy` of config
+    Nois {},
+    // generic VRF oracle
+    Vrf { oracle: Addr },
+    // local mock oracle contract
+    Mock { oracle: Addr },
+}
+
+impl Default for RandomnessProvider {
+    fn default() -> Self {
+        RandomnessProvider::Nois {}
+    }
+}
+
 #[cw_serde]
 pub struct AdminConfig {
     pub admin: Addr,
@@ -28,6 +60,17 @@ pub struct CollectionReward {
     pub collection_address: String,
     pub token_ids: Vec<String>,
     pub id: u32,
+    // name of the tier the slot belongs to
+    pub tier: Option<String>,
+}
+
+#[cw_serde]
+pub struct VestingSchedule {
+    // seconds before anything is released
+    #[serde(default)]
+    pub cliff: u64,
+    // seconds for won amount to be fully released
+    pub duration: u64,
 }
 
 #[cw_serde]
@@ -36,6 +79,11 @@ pub struct CoinReward {
     pub coin: Coin,
     pub number: u32,
     pub id: u32,
+    // name of the tier the slot belongs to
+    pub tier: Option<String>,
+    // won coins are released by vesting schedule instead of being claimed at once
+    #[serde(default)]
+    pub vesting: Option<VestingSchedule>,
 }
 
 #[cw_serde]
@@ -43,6 +91,11 @@ pub struct TextReward {
     pub label: String,
     pub number: u32,
     pub id: u32,
+    // name of the tier the slot belongs to
+    pub tier: Option<String>,
+    // slot is a "no prize" outcome, counted towards the player's losing streak
+    #[serde(default)]
+    pub is_losing: bool,
 }
 
 #[cw_serde]
@@ -52,6 +105,121 @@ pub struct TokenReward {
     pub amount: Uint128,
     pub number: u32,
     pub id: u32,
+    // name of the tier the slot belongs to
+    pub tier: Option<String>,
+    // won tokens are released by vesting schedule instead of being claimed at once
+    #[serde(default)]
+    pub vesting: Option<VestingSchedule>,
+}
+
+// each item pays a random amount between min and max from the pool
+#[cw_serde]
+pub struct RandomCoinReward {
+    pub label: String,
+    pub denom: String,
+    pub min: Uint128,
+    pub max: Uint128,
+    // funded amount of all items, must cover min amount of each item
+    pub pool: Uint128,
+    // drawn amount, only set on won rewards
+    #[serde(default)]
+    pub amount: Option<Uint128>,
+    pub number: u32,
+    pub id: u32,
+    // name of the tier the slot belongs to
+    pub tier: Option<String>,
+}
+
+// each item pays a random amount between min and max from the pool
+#[cw_serde]
+pub struct RandomTokenReward {
+    pub label: String,
+    pub token_address: String,
+    pub min: Uint128,
+    pub max: Uint128,
+    // funded amount of all items, must cover min amount of each item
+    pub pool: Uint128,
+    // drawn amount, only set on won rewards
+    #[serde(default)]
+    pub amount: Option<Uint128>,
+    pub number: u32,
+    pub id: u32,
+    // name of the tier the slot belongs to
+    pub tier: Option<String>,
+}
+
+// nfts are minted to winner on claim, the wheel must be minter of collection
+#[cw_serde]
+pub struct MintNftReward {
+    pub label: String,
+    pub collection_address: String,
+    // token id of minted nft is `{token_id_prefix}{serial}`
+    pub token_id_prefix: String,
+    // `{token_id}` in token uri is replaced by token id of minted nft
+    pub token_uri: Option<String>,
+    // serial of the next minted nft
+    pub next_serial: u32,
+    // maximum number of nfts minted from slot
+    pub number: u32,
+    pub id: u32,
+    // name of the tier the slot belongs to
+    pub tier: Option<String>,
+}
+
+// winners receive the index of a redemption code, codes are delivered and redeemed off-chain
+#[cw_serde]
+pub struct VoucherReward {
+    pub label: String,
+    // hex encoded sha256 hashes of redemption codes
+    pub code_hashes: Vec<String>,
+    // index of the first code in `code_hashes`
+    #[serde(default)]
+    pub next_index: u32,
+    pub id: u32,
+    // name of the tier the slot belongs to
+    pub tier: Option<String>,
+}
+
+// claiming executes a message on an admin registered contract
+#[cw_serde]
+pub struct ContractCallReward {
+    pub label: String,
+    pub contract_address: String,
+    // json message, `{winner}` is replaced by address of winner
+    pub msg: String,
+    pub number: u32,
+    pub id: u32,
+    // name of the tier the slot belongs to
+    pub tier: Option<String>,
+}
+
+#[cw_serde]
+pub struct NftAsset {
+    pub collection_address: String,
+    pub token_id: String,
+}
+
+#[cw_serde]
+pub struct TokenAsset {
+    pub token_address: String,
+    pub amount: Uint128,
+}
+
+// assets delivered together as one reward item
+#[cw_serde]
+pub struct Bundle {
+    pub nfts: Vec<NftAsset>,
+    pub tokens: Vec<TokenAsset>,
+    pub coins: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct BundleReward {
+    pub label: String,
+    pub bundles: Vec<Bundle>,
+    pub id: u32,
+    // name of the tier the slot belongs to
+    pub tier: Option<String>,
 }
 
 #[cw_serde]
@@ -60,40 +228,490 @@ pub enum WheelReward {
     FungibleToken(TokenReward),
     Coin(CoinReward),
     Text(TextReward),
+    Bundle(BundleReward),
+    RandomCoin(RandomCoinReward),
+    RandomToken(RandomTokenReward),
+    MintNft(MintNftReward),
+    Voucher(VoucherReward),
+    ContractCall(ContractCallReward),
 }
 
 impl WheelReward {
     pub fn get_supply(&self) -> u32 {
         match (*self).clone() {
-            Self::NftCollection(colecttion) => {
-                return colecttion.token_ids.len() as u32;
-            }
-            Self::FungibleToken(token) => {
-                return token.number;
-            }
-            Self::Coin(coin) => {
-                return coin.number;
-            }
-            Self::Text(text) => {
-                return text.number;
+            Self::NftCollection(colecttion) => colecttion.token_ids.len() as u32,
+            Self::FungibleToken(token) => token.number,
+            Self::Coin(coin) => coin.number,
+            Self::Text(text) => text.number,
+            Self::Bundle(bundle) => bundle.bundles.len() as u32,
+            Self::RandomCoin(coin) => coin.number,
+            Self::RandomToken(token) => token.number,
+            Self::MintNft(nft) => nft.number,
+            Self::Voucher(voucher) => voucher.code_hashes.len() as u32,
+            Self::ContractCall(call) => call.number,
+        }
+    }
+
+    pub fn get_tier(&self) -> Option<String> {
+        match self {
+            Self::NftCollection(collection) => collection.tier.clone(),
+            Self::FungibleToken(token) => token.tier.clone(),
+            Self::Coin(coin) => coin.tier.clone(),
+            Self::Text(text) => text.tier.clone(),
+            Self::Bundle(bundle) => bundle.tier.clone(),
+            Self::RandomCoin(coin) => coin.tier.clone(),
+            Self::RandomToken(token) => token.tier.clone(),
+            Self::MintNft(nft) => nft.tier.clone(),
+            Self::Voucher(voucher) => voucher.tier.clone(),
+            Self::ContractCall(call) => call.tier.clone(),
+        }
+    }
+
+    pub fn get_label(&self) -> String {
+        match self {
+            Self::NftCollection(collection) => collection.label.clone(),
+            Self::FungibleToken(token) => token.label.clone(),
+            Self::Coin(coin) => coin.label.clone(),
+            Self::Text(text) => text.label.clone(),
+            Self::Bundle(bundle) => bundle.label.clone(),
+            Self::RandomCoin(coin) => coin.label.clone(),
+            Self::RandomToken(token) => token.label.clone(),
+            Self::MintNft(nft) => nft.label.clone(),
+            Self::Voucher(voucher) => voucher.label.clone(),
+            Self::ContractCall(call) => call.label.clone(),
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        match self {
+            Self::NftCollection(collection) => collection.id,
+            Self::FungibleToken(token) => token.id,
+            Self::Coin(coin) => coin.id,
+            Self::Text(text) => text.id,
+            Self::Bundle(bundle) => bundle.id,
+            Self::RandomCoin(coin) => coin.id,
+            Self::RandomToken(token) => token.id,
+            Self::MintNft(nft) => nft.id,
+            Self::Voucher(voucher) => voucher.id,
+            Self::ContractCall(call) => call.id,
+        }
+    }
+
+    pub fn get_kind(&self) -> &'static str {
+        match self {
+            Self::NftCollection(_) => "nft_collection",
+            Self::FungibleToken(_) => "fungible_token",
+            Self::Coin(_) => "coin",
+            Self::Text(_) => "text",
+            Self::Bundle(_) => "bundle",
+            Self::RandomCoin(_) => "random_coin",
+            Self::RandomToken(_) => "random_token",
+            Self::MintNft(_) => "mint_nft",
+            Self::Voucher(_) => "voucher",
+            Self::ContractCall(_) => "contract_call",
+        }
+    }
+
+    /// Returns the assets (denom, contract address or code hash) and total amounts of the reward
+    pub fn get_assets(&self) -> StdResult<Vec<(String, Uint128)>> {
+        let assets = match self {
+            Self::NftCollection(collection) => vec![(
+                collection.collection_address.clone(),
+                Uint128::from(collection.token_ids.len() as u128),
+            )],
+            Self::FungibleToken(token) => vec![(
+                token.token_address.clone(),
+                token
+                    .amount
+                    .checked_mul(Uint128::from(token.number))
+                    .map_err(StdError::from)?,
+            )],
+            Self::Coin(coin) => vec![(
+                coin.coin.denom.clone(),
+                coin.coin
+                    .amount
+                    .checked_mul(Uint128::from(coin.number))
+                    .map_err(StdError::from)?,
+            )],
+            Self::RandomCoin(coin) => vec![(coin.denom.clone(), coin.amount.unwrap_or(coin.pool))],
+            Self::RandomToken(token) => vec![(
+                token.token_address.clone(),
+                token.amount.unwrap_or(token.pool),
+            )],
+            Self::MintNft(nft) => vec![(nft.collection_address.clone(), Uint128::from(nft.number))],
+            Self::ContractCall(call) => {
+                vec![(call.contract_address.clone(), Uint128::from(call.number))]
             }
+            // each asset of each bundle
+            Self::Bundle(bundle) => bundle
+                .bundles
+                .iter()
+                .flat_map(|bundle| {
+                    let nfts = bundle
+                        .nfts
+                        .iter()
+                        .map(|nft| (nft.collection_address.clone(), Uint128::one()));
+                    let tokens = bundle
+                        .tokens
+                        .iter()
+                        .map(|token| (token.token_address.clone(), token.amount));
+                    let coins = bundle
+                        .coins
+                        .iter()
+                        .map(|coin| (coin.denom.clone(), coin.amount));
+                    nfts.chain(tokens).chain(coins)
+                })
+                .collect(),
+            // each redemption code
+            Self::Voucher(voucher) => voucher
+                .code_hashes
+                .iter()
+                .map(|code_hash| (code_hash.clone(), Uint128::one()))
+                .collect(),
+            // text has no asset, empty attribute values are rejected by the chain
+            Self::Text(_) => vec![],
+        };
+
+        Ok(assets)
+    }
+
+    pub fn is_losing(&self) -> bool {
+        match self {
+            Self::Text(text) => text.is_losing,
+            _ => false,
         }
     }
 }
 pub const WHEEL_REWARDS: Item<(u32, Vec<WheelReward>)> = Item::new("wheel rewards");
 
+#[cw_serde]
+pub struct Tier {
+    pub name: String,
+    // relative chance of picking the tier, regardless of its supply
+    pub weight: u32,
+}
+pub const TIERS: Item<Vec<Tier>> = Item::new("tiers");
+
 #[cw_serde]
 pub struct RandomJob {
     pub player: Addr,
     pub spins: u32,
+    // entropy of spin, mixed with the revealed seed in commit-reveal mode
+    #[serde(default)]
+    pub entropy: Option<String>,
+    // time the spin was submitted
+    #[serde(default)]
+    pub submitted_at: Timestamp,
+    // number of spins paid by free spins
+    #[serde(default)]
+    pub free_spins: u32,
+    // set if the spins are made by an operator
+    #[serde(default)]
+    pub callback: Option<SpinCallback>,
+    // account that paid the spins if it is not the player, refunded if the job is cancelled
+    #[serde(default)]
+    pub payer: Option<Addr>,
+    // number of the job in `JOB_QUEUE`
+    #[serde(default)]
+    pub number: u64,
 }
 pub const RANDOM_JOBS: Map<String, RandomJob> = Map::new("random jobs");
 
+// number of random jobs ever created, job ids are never reused
+pub const JOB_COUNT: Item<u64> = Item::new("job count");
+
+// ids of pending jobs by job number, commit-reveal jobs are settled in this order
+pub const JOB_QUEUE: Map<u64, String> = Map::new("job queue");
+
+#[cw_serde]
+pub struct SpinCommit {
+    // hex encoded sha256 hash of player secret
+    pub commitment: String,
+    pub spins: u32,
+    // block height of commit
+    pub height: u64,
+    // RANDOM_SEED at commit, so the result does not depend on the reveal time
+    pub seed: HexBinary,
+}
+pub const SPIN_COMMITS: Map<Addr, SpinCommit> = Map::new("spin commits");
+
+#[cw_serde]
+pub struct SeedCommitment {
+    // hex encoded sha256 hash of the seed
+    pub seed_hash: String,
+    // hex encoded seed, set once revealed
+    pub seed: Option<String>,
+}
+pub const SEED_COMMITMENT: Item<SeedCommitment> = Item::new("seed commitment");
+
 pub const RANDOM_SEED: Item<[u8; 32]> = Item::new("random seed");
 
 pub const WHITELIST: Map<Addr, u32> = Map::new("whitelist");
 
-pub const SPINS_RESULT: Map<Addr, Vec<(bool, WheelReward)>> = Map::new("spins result");
+// contracts allowed to be called by contract call rewards
+pub const CALL_TARGETS: Map<Addr, bool> = Map::new("call targets");
+
+// contracts allowed to spin for players
+pub const OPERATORS: Map<Addr, bool> = Map::new("operators");
+
+#[cw_serde]
+pub struct SpinCallback {
+    // operator contract receiving results of the spins
+    pub operator: Addr,
+    // passed back to the operator with the results
+    pub data: Option<Binary>,
+}
+
+#[cw_serde]
+pub enum RandomnessMode {
+    Basic,
+    CommitSpin,
+    Advanced,
+    CommitReveal,
+}
+
+#[cw_serde]
+pub struct SpinRecord {
+    // global id of the record, increasing with time
+    pub spin_id: u64,
+    pub player: Addr,
+    // index of the result of player
+    pub index: u32,
+    pub reward: WheelReward,
+    pub is_claimed: bool,
+    pub height: u64,
+    pub time: Timestamp,
+    // price paid for the spin
+    pub price: Coin,
+    pub randomness_mode: RandomnessMode,
+    // id of randomness job which fulfilled the spin, if any
+    pub job_id: Option<String>,
+    pub claimed_at: Option<Timestamp>,
+    pub claim_recipient: Option<Addr>,
+}
+
+pub struct SpinRecordIndexes<'a> {
+    // id of the slot the reward is won from
+    pub slot: MultiIndex<'a, u32, SpinRecord, (Addr, u32)>,
+    pub spin_id: UniqueIndex<'a, u64, SpinRecord, (Addr, u32)>,
+    // time of the record in nanoseconds
+    pub time: MultiIndex<'a, u64, SpinRecord, (Addr, u32)>,
+}
+
+impl<'a> IndexList<SpinRecord> for SpinRecordIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SpinRecord>> + '_> {
+        let v: Vec<&dyn Index<SpinRecord>> = vec![&self.slot, &self.spin_id, &self.time];
+        Box::new(v.into_iter())
+    }
+}
+
+// spin results of players, keyed by player and result index
+pub fn spin_records<'a>() -> IndexedMap<'a, (Addr, u32), SpinRecord, SpinRecordIndexes<'a>> {
+    let indexes = SpinRecordIndexes {
+        slot: MultiIndex::new(
+            |_pk, record| record.reward.get_id(),
+            "spin records",
+            "spin records__slot",
+        ),
+        spin_id: UniqueIndex::new(|record| record.spin_id, "spin records__spin_id"),
+        time: MultiIndex::new(
+            |_pk, record| record.time.nanos(),
+            "spin records",
+            "spin records__time",
+        ),
+    };
+    IndexedMap::new("spin records", indexes)
+}
+
+// number of spin records
+pub const SPIN_COUNT: Item<u64> = Item::new("spin count");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct WheelStats {
+    pub total_spins: u64,
+    pub unique_players: u64,
+    // spin payments collected per denom
+    pub revenue: Vec<Coin>,
+}
+pub const WHEEL_STATS: Item<WheelStats> = Item::new("wheel stats");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct SlotStats {
+    pub won: u32,
+    pub claimed: u32,
+}
+pub const SLOT_STATS: Map<u32, SlotStats> = Map::new("slot stats");
+
+#[cw_serde]
+pub struct Leaderboard {
+    // denom the prize values of slots are measured in
+    pub denom: String,
+    // bonus paid to the top players by prize value after the round, by rank
+    pub bonus: Vec<Uint128>,
+    pub is_bonus_paid: bool,
+}
+pub const LEADERBOARD: Item<Leaderboard> = Item::new("leaderboard");
+
+// prize value of each slot in leaderboard denom
+pub const SLOT_VALUES: Map<u32, Uint128> = Map::new("slot values");
+
+#[cw_serde]
+pub struct PlayerScore {
+    pub player: Addr,
+    // accumulated prize value
+    pub value: Uint128,
+    pub spins: u32,
+}
+
+pub struct PlayerScoreIndexes<'a> {
+    pub value: MultiIndex<'a, u128, PlayerScore, Addr>,
+    pub spins: MultiIndex<'a, u32, PlayerScore, Addr>,
+}
+
+impl<'a> IndexList<PlayerScore> for PlayerScoreIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<PlayerScore>> + '_> {
+        let v: Vec<&dyn Index<PlayerScore>> = vec![&self.value, &self.spins];
+        Box::new(v.into_iter())
+    }
+}
+
+// leaderboard scores of players
+pub fn player_scores<'a>() -> IndexedMap<'a, Addr, PlayerScore, PlayerScoreIndexes<'a>> {
+    let indexes = PlayerScoreIndexes {
+        value: MultiIndex::new(
+            |_pk, score| score.value.u128(),
+            "player scores",
+            "player scores__value",
+        ),
+        spins: MultiIndex::new(
+            |_pk, score| score.spins,
+            "player scores",
+            "player scores__spins",
+        ),
+    };
+    IndexedMap::new("player scores", indexes)
+}
+
+#[cw_serde]
+pub struct SpinProof {
+    // seed the randomness of spins is derived from
+    pub seed: HexBinary,
+    // key deriving the randomness of spins from the seed
+    pub key: String,
+    // position of the spin randomness in the sequence derived by the key
+    pub nonce: u32,
+    // randomness of the spin
+    pub randomness: HexBinary,
+    // index of the selected slot
+    pub slot_index: u32,
+    // index of the selected tier, slot is then selected with randomness derived by key `tier`
+    pub tier_index: Option<u32>,
+    // sha256 of the json encoded weighted list of (slot index, weight, tier index) the spin is selected from
+    pub weights_hash: HexBinary,
+}
+// proof of each spin result of player, keyed by result index
+pub const SPIN_PROOFS: Map<(Addr, u32), SpinProof> = Map::new("spin proofs");
+
+// number of consecutive losing results of each player
+pub const LOSING_STREAKS: Map<Addr, u32> = Map::new("losing streaks");
+
+#[cw_serde]
+pub struct Jackpot {
+    pub label: String,
+    pub denom: String,
+    // amount the pool is reset to after each win
+    pub seed_amount: Uint128,
+    // share of each spin payment added to the pool
+    pub contribution_rate: Decimal,
+    // probability of winning the jackpot on each spin
+    pub probability: Decimal,
+    // current prize of the jackpot
+    pub pool: Uint128,
+    // coins funded by admin for reseeding the pool
+    pub reserve: Uint128,
+}
+pub const JACKPOT: Item<Jackpot> = Item::new("jackpot");
+
+// address allowed to attest voucher redemptions besides admin
+pub const VOUCHER_ORACLE: Item<Addr> = Item::new("voucher oracle");
+
+#[cw_serde]
+pub struct VoucherRedemption {
+    pub player: Addr,
+    // revealed redemption code, its hash matches the committed one
+    pub code: String,
+    pub attested_by: Addr,
+    pub redeemed_at: Timestamp,
+}
+// redemptions by slot id and code index
+pub const VOUCHER_REDEMPTIONS: Map<(u32, u32), VoucherRedemption> = Map::new("voucher redemptions");
+
+#[cw_serde]
+pub enum VestingAsset {
+    Coin(Coin),
+    Token(TokenAsset),
+}
+
+impl VestingAsset {
+    pub fn amount(&self) -> Uint128 {
+        match self {
+            Self::Coin(coin) => coin.amount,
+            Self::Token(token) => token.amount,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct VestingRecord {
+    pub label: String,
+    pub asset: VestingAsset,
+    // amount already sent to player
+    pub released: Uint128,
+    pub start: Timestamp,
+    pub cliff: u64,
+    pub duration: u64,
+}
+
+impl VestingRecord {
+    pub fn vested(&self, now: Timestamp) -> Uint128 {
+        let elapsed = now.seconds().saturating_sub(self.start.seconds());
+        if elapsed < self.cliff {
+            return Uint128::zero();
+        }
+        if elapsed >= self.duration {
+            return self.asset.amount();
+        }
+
+        self.asset.amount().multiply_ratio(elapsed, self.duration)
+    }
+}
+pub const VESTINGS: Map<Addr, Vec<VestingRecord>> = Map::new("vestings");
+
+#[cw_serde]
+pub enum ReferralReward {
+    // share of the first paid spins fee of referred player
+    FeeShare { rate: Decimal },
+    // free spins for the referrer
+    FreeSpins { spins: u32 },
+}
+pub const REFERRAL_REWARD: Item<ReferralReward> = Item::new("referral reward");
+
+#[cw_serde]
+pub struct Referral {
+    pub referrer: Addr,
+    // referrer is rewarded once, on the first paid spins of referred player
+    pub is_rewarded: bool,
+}
+// referral of each referred player, saved only once
+pub const REFERRALS: Map<Addr, Referral> = Map::new("referrals");
+
+// fee shares of referrers, locked until they are claimed
+pub const REFERRAL_EARNINGS: Map<Addr, Vec<Coin>> = Map::new("referral earnings");
+
+// free spins of players, used before paid spins
+pub const FREE_SPINS: Map<Addr, u32> = Map::new("free spins");
 
 // locked coins
 pub const LOCKED_COINS: Map<String, Uint128> = Map::new("locked coins");